@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, CouncilConfig, DemocracyConfig, GenesisConfig,
+	GrandpaConfig, Signature, SudoConfig, SystemConfig, TemplateModuleConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -150,5 +150,10 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		},
+		// The council starts empty, members are set by sudo.
+		council: CouncilConfig::default(),
+		democracy: DemocracyConfig::default(),
+		// No permissions are granted, sudo grants them with `templateModule.grantPermission`.
+		template_module: TemplateModuleConfig::default(),
 	}
 }
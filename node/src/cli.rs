@@ -35,6 +35,10 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Print the ink! bindings of the runtime's chain extension.
+	#[structopt(name = "chain-extension-bindings")]
+	ChainExtensionBindings,
+
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
@@ -7,13 +7,59 @@
 
 use std::sync::Arc;
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{
+	chain_extension::ChainExtensionApi as ChainExtensionRuntimeApi, opaque::Block, AccountId,
+	Balance, BlockNumber, Hash, Index,
+};
 use pallet_contracts_rpc::{Contracts, ContractsApi};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+/// RPC methods describing the chain extension available to contracts.
+#[rpc]
+pub trait ChainExtensionApi<BlockHash> {
+	/// SCALE-encoded metadata of the chain extension functions: their ids, names, input and output
+	/// types, whether they change state and their weight formulas.
+	#[rpc(name = "chainExtension_metadata")]
+	fn metadata(&self, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// Implements the `ChainExtensionApi` RPC trait by calling into the runtime.
+pub struct ChainExtension<C> {
+	client: Arc<C>,
+}
+
+impl<C> ChainExtension<C> {
+	/// Create new `ChainExtension` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> ChainExtensionApi<<Block as BlockT>::Hash> for ChainExtension<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ChainExtensionRuntimeApi<Block>,
+{
+	fn metadata(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Bytes> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().metadata(&at).map(Into::into).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to query chain extension metadata.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -36,6 +82,7 @@ where
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: ChainExtensionRuntimeApi<Block>,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -52,7 +99,9 @@ where
 	// to call into the runtime.
 	// `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
 
-	io.extend_with(ContractsApi::to_delegate(Contracts::new(client)));
+	io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
+
+	io.extend_with(ChainExtensionApi::to_delegate(ChainExtension::new(client)));
 
 	io
 }
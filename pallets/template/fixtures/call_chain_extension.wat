@@ -0,0 +1,65 @@
+;; Calls a chain extension function a given number of times and returns the status code and output
+;; of the last call.
+;;
+;; Input: func_id (u32) ++ repeat (u32) ++ the input passed to the chain extension function.
+;; Output: status code (u32) ++ the output written by the chain extension function.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 2 2))
+
+	;; [0, 4) length of the input buffer
+	(data (i32.const 0) "\f8\ff\00\00")
+
+	;; [4, 8) length of the output buffer
+
+	;; [8, 65536) input buffer
+
+	;; [65536, 65540) status code of the last chain extension call
+
+	;; [65540, 131072) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(local $repeat i32)
+		(call $seal_input (i32.const 8) (i32.const 0))
+		(local.set $repeat (i32.load (i32.const 12)))
+
+		(block $done
+			(loop $next
+				(br_if $done (i32.eqz (local.get $repeat)))
+
+				;; Each call overwrites the length with the amount of output written
+				(i32.store (i32.const 4) (i32.const 65532))
+				(i32.store
+					(i32.const 65536)
+					(call $seal_call_chain_extension
+						(i32.load (i32.const 8)) ;; func_id
+						(i32.const 16) ;; input_ptr
+						(i32.sub (i32.load (i32.const 0)) (i32.const 8)) ;; input_len
+						(i32.const 65540) ;; output_ptr
+						(i32.const 4) ;; output_len_ptr
+					)
+				)
+
+				(local.set $repeat (i32.sub (local.get $repeat) (i32.const 1)))
+				(br $next)
+			)
+		)
+
+		;; Nothing was written if the function was never called
+		(if (i32.eqz (i32.load (i32.const 12)))
+			(then (i32.store (i32.const 4) (i32.const 0)))
+		)
+
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 65536)
+			(i32.add (i32.load (i32.const 4)) (i32.const 4))
+		)
+	)
+)
@@ -0,0 +1,79 @@
+;; Stand-in for the `set_value` and `store_in_runtime` messages of the example contract in
+;; `smart-contracts/example-extension`, with the same selectors, encoding and `ResultNum` event, so
+;; the pallet can be tested calling them without building the contract with `cargo-contract`.
+;;
+;; Input: selector ([u8; 4]) ++ value (u32), as passed by `call_smart_contract`.
+;; Output: the SCALE-encoded `Result<(), ContractError>` returned by the message.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_set_storage" (func $seal_set_storage (param i32 i32 i32)))
+	(import "seal0" "seal_deposit_event" (func $seal_deposit_event (param i32 i32 i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the input buffer
+	(data (i32.const 0) "\40")
+
+	;; [4, 8) length of the output buffer of the chain extension, which writes nothing
+
+	;; [8, 72) input buffer
+
+	;; [100, 132) key of `stored_number`, the first and only field of the contract's storage
+
+	;; [200, 205) `ResultNum` event: index of the event ++ number
+	;; [210, 211) topics of the event, none
+
+	;; [300, 302) result of the message: `Ok(())`, or `Err` ++ index of the `ContractError`
+
+	(func (export "deploy"))
+
+	(func $result_num (param $number i32)
+		(i32.store (i32.const 201) (local.get $number))
+		(call $seal_deposit_event (i32.const 210) (i32.const 1) (i32.const 200) (i32.const 5))
+	)
+
+	(func (export "call")
+		(local $status i32)
+		(call $seal_input (i32.const 8) (i32.const 0))
+
+		;; set_value, selector 0x00abcdef
+		(if (i32.eq (i32.load (i32.const 8)) (i32.const 0xefcdab00))
+			(then
+				(call $seal_set_storage (i32.const 100) (i32.const 12) (i32.const 4))
+				(call $result_num (i32.load (i32.const 12)))
+				(call $seal_return (i32.const 0) (i32.const 300) (i32.const 1))
+			)
+		)
+
+		;; store_in_runtime, selector 0x84dd8b4a
+		(if (i32.eq (i32.load (i32.const 8)) (i32.const 0x4a8bdd84))
+			(then
+				;; do_store_in_runtime
+				(local.set $status
+					(call $seal_call_chain_extension
+						(i32.const 1) ;; func_id
+						(i32.const 12) ;; input_ptr
+						(i32.const 4) ;; input_len
+						(i32.const 0) ;; output_ptr
+						(i32.const 4) ;; output_len_ptr
+					)
+				)
+				(if (i32.eqz (local.get $status))
+					(then
+						(call $result_num (i32.load (i32.const 12)))
+						(call $seal_return (i32.const 0) (i32.const 300) (i32.const 1))
+					)
+				)
+				;; The `ContractError` of status code `n` is its variant `n + 2`
+				(i32.store8 (i32.const 300) (i32.const 1))
+				(i32.store8 (i32.const 301) (i32.add (local.get $status) (i32.const 2)))
+				(call $seal_return (i32.const 0) (i32.const 300) (i32.const 2))
+			)
+		)
+
+		unreachable
+	)
+)
@@ -3,16 +3,31 @@
 use super::*;
 
 #[allow(unused)]
-use crate::{ContractEntry, Pallet as Template};
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::{
+	ContractEntry, EvaluationResults, Evaluations, FedValues, Pallet as Template, Permissions,
+	Requests, Sponsorships, Subscriptions,
+};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Currency;
 use frame_system::RawOrigin;
 use pallet_contracts::chain_extension::UncheckedFrom;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec;
+
+/// Builds the runtime types the benchmarks need.
+pub trait BenchmarkHelper<EventFilter> {
+	/// Filter matching the `CalledPalletFromContract` events of `insert_number`, which the
+	/// benchmarks deposit.
+	fn event_filter() -> EventFilter;
+}
 
 benchmarks! {
 	where_clause {
 		where
 		T::AccountId: UncheckedFrom<T::Hash>,
 		T::AccountId: AsRef<[u8]>,
+		T::OracleKey: Default,
+		T::OracleValue: From<u32>,
 	 }
 	insert_number {
 		let s in 0 .. 4294967295;
@@ -21,6 +36,185 @@ benchmarks! {
 	verify {
 		assert_eq!(ContractEntry::<T>::get(), s);
 	}
+
+	// Every event matches every subscription, until the delivery queue is full
+	match_events {
+		let s in 0 .. T::MaxSubscriptions::get();
+		let e in 0 .. T::MaxMatchedEvents::get();
+		for i in 0 .. s {
+			subscribe_funded::<T>(account("subscriber", i, 0))?;
+		}
+		for i in 0 .. e {
+			Template::<T>::deposit_event(Event::CalledPalletFromContract(i));
+		}
+	}: {
+		Template::<T>::match_events(s);
+	}
+
+	// The requesting contract doesn't exist, calling it back fails right away
+	fulfil_request {
+		let n in 0 .. T::MaxRequestResultLen::get();
+		let id = request_funded::<T>(account("requester", 0, 0))?;
+		let origin = T::FulfilOrigin::successful_origin();
+	}: _<T::Origin>(origin, id, vec![0; n as usize], T::MinRequestGasLimit::get())
+	verify {
+		assert!(Requests::<T>::get(id).is_none());
+	}
+
+	expire_request {
+		let id = request_funded::<T>(account("requester", 0, 0))?;
+		let expires_at = frame_system::Pallet::<T>::block_number() + T::RequestTimeout::get();
+		frame_system::Pallet::<T>::set_block_number(expires_at);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(Requests::<T>::get(id).is_none());
+	}
+
+	// Other contracts are evaluated, up to the maximum but one
+	register_evaluation {
+		let n in 0 .. T::MaxEvaluationInputLen::get();
+		register_other_evaluations::<T>()?;
+		let contract: T::AccountId = account("evaluated", 0, 0);
+		let origin = T::EvaluationOrigin::successful_origin();
+	}: _<T::Origin>(origin, contract.clone(), vec![0; n as usize], 1u32.into())
+	verify {
+		assert!(Evaluations::<T>::get().iter().any(|e| e.contract == contract));
+	}
+
+	deregister_evaluation {
+		register_other_evaluations::<T>()?;
+		let contract: T::AccountId = account("evaluated", 0, 0);
+		let origin = T::EvaluationOrigin::successful_origin();
+		Template::<T>::register_evaluation(origin.clone(), contract.clone(), vec![], 1u32.into())
+			.map_err(|_| "Failed to register evaluation.")?;
+	}: _<T::Origin>(origin, contract.clone())
+	verify {
+		assert!(!Evaluations::<T>::get().iter().any(|e| e.contract == contract));
+	}
+
+	// The signature of the evaluator is checked before the transaction is dispatched, by
+	// `validate_unsigned`, so only recording the result is weighed
+	submit_evaluation {
+		let n in 0 .. T::MaxEvaluationOutputLen::get();
+		register_other_evaluations::<T>()?;
+		let contract: T::AccountId = account("evaluated", 0, 0);
+		let origin = T::EvaluationOrigin::successful_origin();
+		Template::<T>::register_evaluation(origin, contract.clone(), vec![], 1u32.into())
+			.map_err(|_| "Failed to register evaluation.")?;
+		Template::<T>::record_evaluation(contract.clone(), Zero::zero(), vec![])
+			.map_err(|_| "Failed to record evaluation.")?;
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+	}: {
+		Template::<T>::record_evaluation(contract.clone(), 1u32.into(), vec![0; n as usize])
+			.map_err(|_| "Failed to record evaluation.")?;
+	}
+	verify {
+		assert_eq!(EvaluationResults::<T>::get(&contract).unwrap().output.len(), n as usize);
+	}
+
+	// Every other feeder fed a fresh value for the key, so all values are aggregated
+	feed_value {
+		let key = T::OracleKey::default();
+		for i in 1 .. T::MaxFeedersPerKey::get() {
+			Template::<T>::feed(account("feeder", i, 0), key, i.into())
+				.map_err(|_| "Failed to feed value.")?;
+		}
+	}: {
+		Template::<T>::feed(account("feeder", 0, 0), key, 0u32.into())
+			.map_err(|_| "Failed to feed value.")?;
+	}
+	verify {
+		assert_eq!(FedValues::<T>::get(key).len() as u32, T::MaxFeedersPerKey::get());
+	}
+
+	// The caller already sponsors the message, so its quota is replaced
+	// A new sponsorship, reserving its deposit
+	sponsor_calls {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_sponsor::<T>(&caller);
+		let contract: T::AccountId = account("sponsored", 0, 0);
+	}: _ (RawOrigin::Signed(caller.clone()), contract.clone(), [1u8; 4], 2)
+	verify {
+		assert_eq!(Sponsorships::<T>::get((contract, [1u8; 4])).unwrap().quota, 2);
+	}
+
+	withdraw_sponsorship {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_sponsor::<T>(&caller);
+		let contract: T::AccountId = account("sponsored", 0, 0);
+		let origin = RawOrigin::Signed(caller.clone()).into();
+		Template::<T>::sponsor_calls(origin, contract.clone(), [1u8; 4], 1)
+			.map_err(|_| "Failed to sponsor calls.")?;
+	}: _ (RawOrigin::Signed(caller.clone()), contract.clone(), [1u8; 4])
+	verify {
+		assert!(Sponsorships::<T>::get((contract, [1u8; 4])).is_none());
+	}
+
+	// The subscriber is not a contract, as if it was terminated
+	clear_subscriptions {
+		let caller: T::AccountId = whitelisted_caller();
+		let subscriber: T::AccountId = account("subscriber", 0, 0);
+		subscribe_funded::<T>(subscriber.clone())?;
+	}: _ (RawOrigin::Signed(caller), subscriber)
+	verify {
+		assert!(Subscriptions::<T>::get().is_empty());
+	}
+
+	grant_permission {
+		let account: T::AccountId = account("permitted", 0, 0);
+		let origin = T::PermissionOrigin::successful_origin();
+	}: _<T::Origin>(origin, account.clone(), Permission::GovernanceContract)
+	verify {
+		assert!(Permissions::<T>::contains_key(account, Permission::GovernanceContract));
+	}
+
+	revoke_permission {
+		let account: T::AccountId = account("permitted", 0, 0);
+		let origin = T::PermissionOrigin::successful_origin();
+		Template::<T>::grant_permission(origin, account.clone(), Permission::GovernanceContract)
+			.map_err(|_| "Failed to grant permission.")?;
+		let origin = T::PermissionOrigin::successful_origin();
+	}: _<T::Origin>(origin, account.clone(), Permission::GovernanceContract)
+	verify {
+		assert!(!Permissions::<T>::contains_key(account, Permission::GovernanceContract));
+	}
+}
+
+/// Subscribe `subscriber` to the events matched by the benchmarks, funding its deposit.
+fn subscribe_funded<T: Config>(subscriber: T::AccountId) -> Result<(), &'static str> {
+	let deposit = T::SubscriptionDeposit::get();
+	<T as Config>::Currency::make_free_balance_be(&subscriber, deposit.saturating_add(deposit));
+	Template::<T>::subscribe(subscriber, T::BenchmarkHelper::event_filter(), [0; 4])
+		.map_err(|_| "Failed to subscribe.")
+}
+
+/// Make a request of `requester` with the minimum gas limit, funding its deposit.
+fn request_funded<T: Config>(requester: T::AccountId) -> Result<RequestId, &'static str> {
+	let deposit = T::RequestDeposit::get();
+	<T as Config>::Currency::make_free_balance_be(&requester, deposit.saturating_add(deposit));
+	Template::<T>::request(requester, [0; 4], T::MinRequestGasLimit::get(), vec![])
+		.map_err(|_| "Failed to request.")
+}
+
+/// Fund `sponsor` with the deposit of a sponsorship.
+fn fund_sponsor<T: Config>(sponsor: &T::AccountId) {
+	let deposit = T::SponsorshipDeposit::get();
+	<T as Config>::Currency::make_free_balance_be(sponsor, deposit.saturating_add(deposit));
+}
+
+/// Register the evaluation of other contracts, up to the maximum but one.
+fn register_other_evaluations<T: Config>() -> Result<(), &'static str>
+where
+	T::AccountId: UncheckedFrom<T::Hash>,
+	T::AccountId: AsRef<[u8]>,
+{
+	for i in 1..T::MaxEvaluations::get() {
+		let origin = T::EvaluationOrigin::successful_origin();
+		Template::<T>::register_evaluation(origin, account("evaluated", i, 0), vec![], 1u32.into())
+			.map_err(|_| "Failed to register evaluation.")?;
+	}
+	Ok(())
 }
 
 impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -13,30 +13,567 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
 pub mod weights;
 pub use weights::WeightInfo;
 
+use codec::{Compact, Decode, Encode};
+use frame_support::{
+	traits::{Contains, EnsureOrigin, Get, IsSubType},
+	weights::Weight,
+};
+use frame_system::offchain::{SignedPayload, SigningTypes};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError},
+	DispatchResult, FixedPointOperand,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Key type of the keys the offchain worker signs the results of evaluated contracts with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmpl");
+
+/// The offchain worker's keys, which must be inserted in the keystore of the nodes running it,
+/// e.g. with the `author_insertKey` RPC.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Identifies the keys signing the results of evaluated contracts, see
+	/// `Config::AuthorityId`.
+	pub struct EvaluatorId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for EvaluatorId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Whether contracts called by the pallet may call back into it, e.g. through the chain extension.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub enum ReentrancyPolicy {
+	/// Contracts called by the pallet may call back into it, up to `Config::MaxCallDepth` nested
+	/// contract calls.
+	Allow,
+	/// Contracts called by the pallet may not call back into it.
+	Deny,
+}
+
+/// Origin of the calls a contract dispatches through the chain extension, so pallets can require
+/// them to come from a given contract or contract code, see `EnsureContract`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct ContractOrigin<AccountId, Hash> {
+	/// Address of the contract.
+	pub address: AccountId,
+	/// Hash of the code executed by the contract.
+	pub code_hash: Hash,
+}
+
+/// Ensures a call was dispatched by the contract at `Address`, succeeding with its address.
+pub struct EnsureContract<T, Address>(PhantomData<(T, Address)>);
+
+impl<O, T, Address> EnsureOrigin<O> for EnsureContract<T, Address>
+where
+	O: Into<Result<Origin<T>, O>> + From<Origin<T>>,
+	T: frame_system::Config,
+	Address: Get<T::AccountId>,
+{
+	type Success = T::AccountId;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|origin| {
+			if origin.address == Address::get() {
+				Ok(origin.address)
+			} else {
+				Err(O::from(origin))
+			}
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(ContractOrigin { address: Address::get(), code_hash: Default::default() })
+	}
+}
+
+/// Ensures a call was dispatched by a contract executing the code with hash `CodeHash`, succeeding
+/// with the address of the contract.
+pub struct EnsureContractWithCodeHash<T, CodeHash>(PhantomData<(T, CodeHash)>);
+
+impl<O, T, CodeHash> EnsureOrigin<O> for EnsureContractWithCodeHash<T, CodeHash>
+where
+	O: Into<Result<Origin<T>, O>> + From<Origin<T>>,
+	T: frame_system::Config,
+	CodeHash: Get<T::Hash>,
+{
+	type Success = T::AccountId;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|origin| {
+			if origin.code_hash == CodeHash::get() {
+				Ok(origin.address)
+			} else {
+				Err(O::from(origin))
+			}
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(ContractOrigin { address: Default::default(), code_hash: CodeHash::get() })
+	}
+}
+
+/// Permissions `Config::PermissionOrigin` grants to accounts, checked by the runtime through
+/// `HasPermission`.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Permission {
+	/// Contracts taking part in governance through the chain extension.
+	GovernanceContract,
+	/// Accounts whose keys may sign the results of the contracts evaluated by the offchain
+	/// worker.
+	Evaluator,
+	/// Accounts feeding values to the oracle.
+	OracleFeeder,
+}
+
+/// Accounts granted the permission `P`.
+pub struct HasPermission<T, P>(PhantomData<(T, P)>);
+
+impl<T: Config, P: Get<Permission>> Contains<T::AccountId> for HasPermission<T, P> {
+	fn contains(account: &T::AccountId) -> bool {
+		Pallet::<T>::has_permission(account, P::get())
+	}
+}
+
+/// Matches the runtime events contracts subscribe to, see `Config::EventFilter`.
+pub trait MatchEvent<Event> {
+	/// Whether `event` is one of the events matched.
+	fn matches(&self, event: &Event) -> bool;
+}
+
+/// Subscription of a contract to the runtime events matching `filter`, each of which is passed to
+/// its message with `selector`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct Subscription<AccountId, EventFilter> {
+	/// Address of the subscribed contract.
+	pub contract: AccountId,
+	/// Events the contract is called with.
+	pub filter: EventFilter,
+	/// Selector of the message called.
+	pub selector: [u8; 4],
+}
+
+/// Call of a subscribed contract with a matching event, queued until there is weight left for it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct Delivery<AccountId> {
+	/// Address of the subscribed contract.
+	pub contract: AccountId,
+	/// Selector of the message called.
+	pub selector: [u8; 4],
+	/// The SCALE-encoded runtime event, passed to the message as a `Vec<u8>`.
+	pub event: Vec<u8>,
+}
+
+/// Identifier of a request of a contract, see `Pallet::request`.
+pub type RequestId = u64;
+
+/// Request of a contract for a result only available later, such as off-chain data, waiting for
+/// `FulfilOrigin` to fulfil it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct Request<AccountId, BlockNumber> {
+	/// Address of the requesting contract.
+	pub contract: AccountId,
+	/// Selector of the message called back with the result.
+	pub selector: [u8; 4],
+	/// Gas limit of the call back, which the contract paid for when requesting.
+	pub gas_limit: Weight,
+	/// What the contract requests, in a format agreed on with the fulfillers.
+	pub payload: Vec<u8>,
+	/// Block from which anyone may expire the request if it is still not fulfilled.
+	pub expires_at: BlockNumber,
+}
+
+/// Executes the calls the offchain worker evaluates contracts with, see `Config::Query`.
+pub trait ExecuteQuery {
+	/// Execute `f`, in which contracts are called as read-only queries.
+	fn execute_query<R>(f: impl FnOnce() -> R) -> R;
+}
+
+impl ExecuteQuery for () {
+	fn execute_query<R>(f: impl FnOnce() -> R) -> R {
+		f()
+	}
+}
+
+/// Contract the offchain worker evaluates every `interval` blocks, calling it with `input`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct Evaluation<AccountId, BlockNumber> {
+	/// Address of the evaluated contract.
+	pub contract: AccountId,
+	/// Input of the call, the selector of the message called and its arguments.
+	pub input: Vec<u8>,
+	/// Number of blocks between evaluations.
+	pub interval: BlockNumber,
+}
+
+/// Latest result of an evaluated contract.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct EvaluationResult<BlockNumber> {
+	/// Block in which the contract was evaluated.
+	pub evaluated_at: BlockNumber,
+	/// Output of the message called.
+	pub output: Vec<u8>,
+}
+
+/// Result of an evaluated contract submitted by the offchain worker, signed by `public`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct EvaluationPayload<Public, AccountId, BlockNumber> {
+	/// Address of the evaluated contract.
+	pub contract: AccountId,
+	/// Block in which the contract was evaluated.
+	pub evaluated_at: BlockNumber,
+	/// Output of the message called.
+	pub output: Vec<u8>,
+	/// Key of the evaluator.
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T>
+	for EvaluationPayload<T::Public, T::AccountId, T::BlockNumber>
+{
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// Value fed to the oracle by `feeder`, counted in the aggregated value until it is stale.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct FedValue<AccountId, Value> {
+	/// Account which fed the value.
+	pub feeder: AccountId,
+	/// The value fed.
+	pub value: Value,
+	/// When the value was fed, in milliseconds since the Unix epoch.
+	pub timestamp: u64,
+}
+
+/// Value aggregated by the oracle, with when it was aggregated.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct TimestampedValue<Value> {
+	/// Median of the values fed for the key which were not stale.
+	pub value: Value,
+	/// When the value was aggregated, in milliseconds since the Unix epoch.
+	pub timestamp: u64,
+}
+
+/// Sponsorship of the `call_smart_contract` calls of a contract message by `sponsor`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub struct Sponsorship<AccountId> {
+	/// Account paying the fees of the sponsored calls.
+	pub sponsor: AccountId,
+	/// Calls sponsored for each account per `Config::SponsorshipPeriod`.
+	pub quota: u32,
+}
+
+/// Custom `InvalidTransaction` code of sponsored transactions with a tip, which sponsors don't pay.
+pub const SPONSORED_TIP: u8 = 0;
+
+/// Charges the fees of a transaction as `ChargeTransactionPayment` does, to the sponsor of the
+/// message it calls instead of its signer if it is a sponsored `call_smart_contract` and the
+/// signer has calls left in its quota. Such transactions are refused with a tip. Replaces
+/// `ChargeTransactionPayment` in the runtime's signed extensions, encoded the same, as its tip.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeSponsoredTransactionPayment<T: Config + pallet_transaction_payment::Config>(
+	#[codec(compact)] TipOf<T>,
+);
+
+/// Tip of a transaction, in the currency its fees are paid with.
+pub type TipOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as
+	pallet_transaction_payment::OnChargeTransaction<T>>::Balance;
+
+impl<T: Config + pallet_transaction_payment::Config> ChargeSponsoredTransactionPayment<T> {
+	/// Utility constructor, as for `ChargeTransactionPayment`.
+	pub fn from(tip: TipOf<T>) -> Self {
+		Self(tip)
+	}
+
+	/// Whether the transaction has a tip.
+	fn tips(&self) -> bool {
+		!self.0.is_zero()
+	}
+}
+
+impl<T: Config + pallet_transaction_payment::Config> sp_std::fmt::Debug
+	for ChargeSponsoredTransactionPayment<T>
+{
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeSponsoredTransactionPayment({:?})", self.0)
+	}
+}
+
+impl<T> SignedExtension for ChargeSponsoredTransactionPayment<T>
+where
+	T: Config + pallet_transaction_payment::Config + Send + Sync,
+	TipOf<T>: Send + Sync + FixedPointOperand,
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+	ChargeTransactionPayment<T>: SignedExtension<
+		AccountId = T::AccountId,
+		Call = <T as frame_system::Config>::Call,
+		AdditionalSigned = (),
+	>,
+{
+	// Same identifier as the extension it replaces, so wallets keep encoding the tip for it
+	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = <ChargeTransactionPayment<T> as SignedExtension>::Pre;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		let sponsor = call.is_sub_type().and_then(|call| Pallet::<T>::sponsor_of(who, call));
+		if sponsor.is_some() && self.tips() {
+			return InvalidTransaction::Custom(SPONSORED_TIP).into()
+		}
+		ChargeTransactionPayment::<T>::from(self.0).validate(
+			sponsor.as_ref().unwrap_or(who),
+			call,
+			info,
+			len,
+		)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let sponsored = call
+			.is_sub_type()
+			.and_then(|inner| Some((inner, Pallet::<T>::sponsor_of(who, inner)?)));
+		if sponsored.is_some() && self.tips() {
+			return Err(InvalidTransaction::Custom(SPONSORED_TIP).into())
+		}
+		let payer = sponsored.as_ref().map_or(who, |(_, sponsor)| sponsor);
+		let pre =
+			ChargeTransactionPayment::<T>::from(self.0).pre_dispatch(payer, call, info, len)?;
+		// Only counted against the quota once the sponsor paid
+		if let Some((inner, _)) = sponsored {
+			Pallet::<T>::note_sponsored_call(who, inner);
+		}
+		Ok(pre)
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		// The fee is corrected and refunded to the account it was withdrawn from, kept in `pre`
+		ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
 	use frame_support::{
-		dispatch::DispatchResult, inherent::Vec, pallet_prelude::*, traits::Currency,
+		dispatch::{DispatchResult, DispatchResultWithPostInfo},
+		inherent::Vec,
+		log::{debug, warn},
+		pallet_prelude::*,
+		storage::{storage_prefix, unhashed},
+		traits::{
+			BalanceStatus, Currency, ExistenceRequirement, Imbalance, PalletInfoAccess,
+			ReservableCurrency, UnixTime, WithdrawReasons,
+		},
+		weights::WithPostDispatchInfo,
+		Twox64Concat,
+	};
+	use frame_system::{
+		offchain::{AppCrypto, SendTransactionTypes, SendUnsignedTransaction, Signer},
+		pallet_prelude::*,
+		EventRecord,
 	};
-	use frame_system::pallet_prelude::*;
 	use pallet_contracts::chain_extension::UncheckedFrom;
+	use sp_runtime::traits::{Convert, IdentifyAccount, SaturatedConversion, Saturating};
+	use sp_std::convert::TryInto;
 
 	type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
+	type DepositOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type SubscriptionOf<T> =
+		Subscription<<T as frame_system::Config>::AccountId, <T as Config>::EventFilter>;
+	type RequestOf<T> =
+		Request<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+	type EvaluationOf<T> = Evaluation<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	type FedValueOf<T> =
+		FedValue<<T as frame_system::Config>::AccountId, <T as Config>::OracleValue>;
+	type SponsorshipKey<T> = (<T as frame_system::Config>::AccountId, [u8; 4]);
+	type EvaluationPayloadOf<T> = EvaluationPayload<
+		<T as SigningTypes>::Public,
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_contracts::Config {
+	pub trait Config:
+		frame_system::Config
+		+ pallet_contracts::Config
+		+ SendTransactionTypes<Call<Self>>
+		+ SigningTypes
+	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 		type WeightInfo: WeightInfo;
+		/// Maximum length in bytes of the payload of a `ContractSignal` event.
+		#[pallet::constant]
+		type MaxSignalPayloadLen: Get<u32>;
+		/// Maximum number of nested contract calls made by `call_smart_contract`, counting the
+		/// calls contracts make back into the pallet.
+		#[pallet::constant]
+		type MaxCallDepth: Get<u32>;
+		/// Whether contracts called by `call_smart_contract` may call back into the pallet.
+		type Reentrancy: Get<ReentrancyPolicy>;
+		/// Runtime events contracts may subscribe to.
+		type EventFilter: Parameter + MatchEvent<<Self as frame_system::Config>::Event>;
+		/// Builds the runtime types the benchmarks need.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::EventFilter>;
+		/// Maximum number of subscriptions to runtime events, of all contracts.
+		#[pallet::constant]
+		type MaxSubscriptions: Get<u32>;
+		/// Maximum number of subscriptions to runtime events of a single contract.
+		#[pallet::constant]
+		type MaxSubscriptionsPerContract: Get<u32>;
+		/// Deposit reserved from a contract for each of its subscriptions, returned when it
+		/// unsubscribes.
+		#[pallet::constant]
+		type SubscriptionDeposit: Get<DepositOf<Self>>;
+		/// Maximum number of the events of a block matched against the subscriptions, which the
+		/// weight reserved for matching covers. Later events of the block are not decoded, and
+		/// are counted in `DeliveriesDropped`.
+		#[pallet::constant]
+		type MaxMatchedEvents: Get<u32>;
+		/// Maximum number of events waiting to be delivered to subscribed contracts. Events
+		/// matched once it is reached are dropped.
+		#[pallet::constant]
+		type MaxQueuedDeliveries: Get<u32>;
+		/// Maximum weight of delivering the queued events at the start of a block. Only the
+		/// weight of the deliveries made is used.
+		#[pallet::constant]
+		type DeliveryWeight: Get<Weight>;
+		/// Gas limit of each call delivering an event to a subscribed contract.
+		#[pallet::constant]
+		type DeliveryGasLimit: Get<Weight>;
+		/// Price of the weight of delivering events. Subscribed contracts pay that of the
+		/// `DeliveryGasLimit` of each delivery up front, and get back that of the gas left.
+		type DeliveryWeightPrice: Convert<Weight, DepositOf<Self>>;
+		/// Origin fulfilling the requests of contracts with their result.
+		type FulfilOrigin: EnsureOrigin<Self::Origin>;
+		/// Maximum length in bytes of the payload of a request.
+		#[pallet::constant]
+		type MaxRequestPayloadLen: Get<u32>;
+		/// Maximum length in bytes of the result a request is fulfilled with.
+		#[pallet::constant]
+		type MaxRequestResultLen: Get<u32>;
+		/// Maximum number of requests waiting to be fulfilled, of all contracts.
+		#[pallet::constant]
+		type MaxPendingRequests: Get<u32>;
+		/// Maximum number of requests waiting to be fulfilled of a single contract.
+		#[pallet::constant]
+		type MaxRequestsPerContract: Get<u32>;
+		/// Minimum gas limit of the call back of a request.
+		#[pallet::constant]
+		type MinRequestGasLimit: Get<Weight>;
+		/// Deposit reserved from a contract for each of its requests, returned when the request
+		/// is fulfilled or expired.
+		#[pallet::constant]
+		type RequestDeposit: Get<DepositOf<Self>>;
+		/// Number of blocks after which anyone may expire a request still not fulfilled.
+		#[pallet::constant]
+		type RequestTimeout: Get<Self::BlockNumber>;
+		/// Keys the offchain worker signs the results of evaluated contracts with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Origin registering the contracts evaluated by the offchain worker.
+		type EvaluationOrigin: EnsureOrigin<Self::Origin>;
+		/// How the offchain worker calls the evaluated contracts as read-only queries. Whatever
+		/// they change is discarded anyway, like all changes made by offchain workers.
+		type Query: ExecuteQuery;
+		/// Maximum number of contracts evaluated by the offchain worker.
+		#[pallet::constant]
+		type MaxEvaluations: Get<u32>;
+		/// Maximum length in bytes of the input evaluated contracts are called with.
+		#[pallet::constant]
+		type MaxEvaluationInputLen: Get<u32>;
+		/// Maximum length in bytes of the output of evaluated contracts. Longer outputs are not
+		/// submitted.
+		#[pallet::constant]
+		type MaxEvaluationOutputLen: Get<u32>;
+		/// Gas limit of the calls evaluating contracts, which is not paid for.
+		#[pallet::constant]
+		type EvaluationGasLimit: Get<Weight>;
+		/// Priority of the transactions submitting the results of evaluated contracts.
+		#[pallet::constant]
+		type EvaluationPriority: Get<TransactionPriority>;
+		/// Keys of the values fed to the oracle, such as asset ids.
+		type OracleKey: Parameter + Copy;
+		/// Values fed to the oracle, such as prices.
+		type OracleValue: Parameter + Copy + Ord;
+		/// Time the values fed to the oracle are timestamped with.
+		type OracleTime: UnixTime;
+		/// Maximum number of accounts feeding values for a single key.
+		#[pallet::constant]
+		type MaxFeedersPerKey: Get<u32>;
+		/// Age in milliseconds from which a fed value is stale, and no longer aggregated.
+		#[pallet::constant]
+		type OracleMaxAge: Get<u64>;
+		/// Number of blocks in which the calls of each account sponsored up to the quota of a
+		/// sponsorship are counted.
+		#[pallet::constant]
+		type SponsorshipPeriod: Get<Self::BlockNumber>;
+		/// Deposit reserved from a sponsor for each message it sponsors, returned when it
+		/// withdraws the sponsorship. Only one account may sponsor a message, which the deposit
+		/// keeps from being done for all messages at no cost.
+		#[pallet::constant]
+		type SponsorshipDeposit: Get<DepositOf<Self>>;
+		/// Origin granting and revoking permissions.
+		type PermissionOrigin: EnsureOrigin<Self::Origin>;
 	}
 
 	// Some const value to compare inputs of unknown size to
@@ -46,10 +583,133 @@ pub mod pallet {
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
+	/// Origin of the calls dispatched by the chain extension on behalf of a contract.
+	#[pallet::origin]
+	pub type Origin<T> =
+		ContractOrigin<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_value)]
 	pub(super) type ContractEntry<T> = StorageValue<_, u32, ValueQuery>;
 
+	/// Number of nested contract calls made by `call_smart_contract` being executed. Transient, it
+	/// is only set while a contract called by the pallet executes, and removed once it returns.
+	#[pallet::storage]
+	#[pallet::getter(fn call_depth)]
+	pub(super) type CallDepth<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Subscriptions of contracts to runtime events, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn subscriptions)]
+	pub(super) type Subscriptions<T: Config> =
+		StorageValue<_, BoundedVec<SubscriptionOf<T>, T::MaxSubscriptions>, ValueQuery>;
+
+	/// Events matched by subscriptions, waiting to be delivered to their contract, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn delivery_queue)]
+	pub(super) type DeliveryQueue<T: Config> =
+		StorageValue<_, BoundedVec<Delivery<T::AccountId>, T::MaxQueuedDeliveries>, ValueQuery>;
+
+	/// Number of subscriptions the weight of matching the events of this block was reserved for
+	/// in `on_initialize`. Transient, it is only set between `on_initialize` and `on_finalize`.
+	#[pallet::storage]
+	pub(super) type MatchingScheduled<T> = StorageValue<_, u32, OptionQuery>;
+
+	/// Requests of contracts waiting to be fulfilled, by id.
+	#[pallet::storage]
+	#[pallet::getter(fn requests)]
+	pub(super) type Requests<T: Config> =
+		StorageMap<_, Twox64Concat, RequestId, RequestOf<T>, OptionQuery>;
+
+	/// Number of requests waiting to be fulfilled.
+	#[pallet::storage]
+	pub(super) type PendingRequests<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of requests waiting to be fulfilled of each contract.
+	#[pallet::storage]
+	pub(super) type PendingRequestsOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Id of the next request.
+	#[pallet::storage]
+	pub(super) type NextRequestId<T> = StorageValue<_, RequestId, ValueQuery>;
+
+	/// Contracts evaluated by the offchain worker.
+	#[pallet::storage]
+	#[pallet::getter(fn evaluations)]
+	pub(super) type Evaluations<T: Config> =
+		StorageValue<_, BoundedVec<EvaluationOf<T>, T::MaxEvaluations>, ValueQuery>;
+
+	/// Latest result of each contract evaluated by the offchain worker.
+	#[pallet::storage]
+	#[pallet::getter(fn evaluation_results)]
+	pub(super) type EvaluationResults<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, EvaluationResult<T::BlockNumber>, OptionQuery>;
+
+	/// Values fed to the oracle for each key which were not stale when last fed.
+	#[pallet::storage]
+	#[pallet::getter(fn fed_values)]
+	pub(super) type FedValues<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::OracleKey,
+		BoundedVec<FedValueOf<T>, T::MaxFeedersPerKey>,
+		ValueQuery,
+	>;
+
+	/// Latest value aggregated by the oracle for each key.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_value)]
+	pub(super) type OracleValues<T: Config> =
+		StorageMap<_, Twox64Concat, T::OracleKey, TimestampedValue<T::OracleValue>, OptionQuery>;
+
+	/// Sponsorships of the calls of contract messages, by contract and selector.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorships)]
+	pub(super) type Sponsorships<T: Config> =
+		StorageMap<_, Twox64Concat, SponsorshipKey<T>, Sponsorship<T::AccountId>, OptionQuery>;
+
+	/// Sponsored calls of contract messages made by each account, in the sponsorship period they
+	/// were last made in.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_calls)]
+	pub(super) type SponsoredCalls<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SponsorshipKey<T>,
+		Twox64Concat,
+		T::AccountId,
+		(T::BlockNumber, u32),
+		OptionQuery,
+	>;
+
+	/// Permissions granted to each account.
+	#[pallet::storage]
+	pub(super) type Permissions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, Permission, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Permissions granted at genesis.
+		pub permissions: Vec<(T::AccountId, Permission)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { permissions: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (account, permission) in &self.permissions {
+				Permissions::<T>::insert(account, permission, ());
+			}
+		}
+	}
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events
 	#[pallet::event]
@@ -59,12 +719,192 @@ pub mod pallet {
 		CalledContractFromPallet(T::AccountId),
 		/// Event to display when call is made from a smart contract to the extrinsic
 		CalledPalletFromContract(u32),
+		/// A contract emitted a signal through the chain extension, for other pallets and
+		/// indexers to react to without decoding the contract's own events
+		ContractSignal {
+			contract: T::AccountId,
+			topic: [u8; 32],
+			payload: BoundedVec<u8, T::MaxSignalPayloadLen>,
+		},
+		/// Events were dropped without being delivered, as the delivery queue was full or they
+		/// came after the first `MaxMatchedEvents` of the block
+		DeliveriesDropped(u32),
+		/// Delivering an event to a subscribed contract failed, or it couldn't pay for it
+		DeliveryFailed(T::AccountId),
+		/// A contract made a request, waiting to be fulfilled \[id, contract\]
+		RequestQueued(RequestId, T::AccountId),
+		/// A request was fulfilled and its contract called back with the result
+		RequestFulfilled(RequestId),
+		/// A request was fulfilled but calling its contract back failed
+		RequestCallbackFailed(RequestId),
+		/// A contract is evaluated by the offchain worker
+		EvaluationRegistered(T::AccountId),
+		/// A contract is no longer evaluated by the offchain worker
+		EvaluationDeregistered(T::AccountId),
+		/// The offchain worker submitted a new result of an evaluated contract
+		ContractEvaluated(T::AccountId),
+		/// A value was fed to the oracle, updating the aggregated value \[key, value\]
+		OracleUpdated(T::OracleKey, T::OracleValue),
+		/// An account sponsors the calls of a contract message \[sponsor, contract, selector\]
+		CallsSponsored(T::AccountId, T::AccountId, [u8; 4]),
+		/// An account stopped sponsoring the calls of a contract message
+		/// \[sponsor, contract, selector\]
+		SponsorshipWithdrawn(T::AccountId, T::AccountId, [u8; 4]),
+		/// A permission was granted to an account \[account, permission\]
+		PermissionGranted(T::AccountId, Permission),
+		/// A permission of an account was revoked \[account, permission\]
+		PermissionRevoked(T::AccountId, Permission),
+		/// The subscriptions of a terminated contract were removed \[contract, count\]
+		SubscriptionsCleared(T::AccountId, u32),
+		/// A request was not fulfilled in time and was removed
+		RequestExpired(RequestId),
 	}
 
 	// Errors inform users that something went wrong.
 	#[pallet::error]
 	pub enum Error<T> {
 		InputTooLarge,
+		/// The payload of a contract signal is longer than `MaxSignalPayloadLen`
+		SignalPayloadTooLarge,
+		/// A contract called by the pallet called back into it, which `Reentrancy` denies
+		ReentrancyDenied,
+		/// Calling the contract would exceed `MaxCallDepth` nested contract calls
+		CallDepthExceeded,
+		/// The contract, or all contracts together, have as many subscriptions as allowed
+		TooManySubscriptions,
+		/// The payload of a request is longer than `MaxRequestPayloadLen`
+		RequestPayloadTooLarge,
+		/// The contract, or all contracts together, have as many pending requests as allowed
+		TooManyRequests,
+		/// There is no request waiting to be fulfilled with this id
+		UnknownRequest,
+		/// The result of a request is longer than `MaxRequestResultLen`
+		RequestResultTooLarge,
+		/// The gas limit is lower than the one the request was made with
+		GasLimitTooLow,
+		/// The input of an evaluated contract is longer than `MaxEvaluationInputLen`
+		EvaluationInputTooLarge,
+		/// As many contracts as `MaxEvaluations` are evaluated by the offchain worker
+		TooManyEvaluations,
+		/// The contract is not evaluated by the offchain worker
+		UnknownEvaluation,
+		/// The output of an evaluated contract is longer than `MaxEvaluationOutputLen`
+		EvaluationOutputTooLarge,
+		/// The contract was evaluated less than its interval ago, or in a future block
+		EvaluationNotDue,
+		/// The account was not granted the `OracleFeeder` permission
+		NotOracleFeeder,
+		/// As many accounts as `MaxFeedersPerKey` fed fresh values for the key
+		TooManyOracleFeeders,
+		/// Another account sponsors the calls of the contract message
+		AlreadySponsored,
+		/// No account sponsors the calls of the contract message
+		UnknownSponsorship,
+		/// The account is not the sponsor of the calls of the contract message
+		NotSponsor,
+		/// The permission was not granted to the account
+		PermissionNotGranted,
+		/// The contract can't pay the deposit of a subscription
+		InsufficientSubscriptionDeposit,
+		/// The contract was not terminated, only it may remove its subscriptions
+		ContractNotTerminated,
+		/// The contract has no subscriptions
+		NoSubscriptions,
+		/// The gas limit of a request is lower than `MinRequestGasLimit`
+		RequestGasLimitTooLow,
+		/// The contract can't pay the deposit of a request
+		InsufficientRequestDeposit,
+		/// The request may only be expired `RequestTimeout` blocks after it was made
+		RequestNotExpired,
+		/// The sponsor can't pay the deposit of a sponsorship
+		InsufficientSponsorshipDeposit,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash>,
+		T::AccountId: AsRef<[u8]>,
+	{
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Finding out whether there are queued deliveries and subscriptions
+			let mut weight = T::DbWeight::get().reads(2);
+			if DeliveryQueue::<T>::decode_len().unwrap_or(0) > 0 {
+				weight = weight.saturating_add(Self::deliver_events(T::DeliveryWeight::get()));
+			}
+
+			// `on_finalize` can't return the weight it uses, so matching the events of the block
+			// is reserved now, for the subscriptions there are
+			let subscriptions = Subscriptions::<T>::decode_len().unwrap_or(0) as u32;
+			if subscriptions > 0 {
+				MatchingScheduled::<T>::put(subscriptions);
+				weight = weight
+					.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+					.saturating_add(<T as Config>::WeightInfo::match_events(
+						subscriptions,
+						T::MaxMatchedEvents::get(),
+					));
+			}
+			weight
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			if let Some(subscriptions) = MatchingScheduled::<T>::take() {
+				Self::match_events(subscriptions);
+			}
+		}
+
+		fn offchain_worker(n: T::BlockNumber) {
+			if let Err(e) = Self::evaluate_contracts(n) {
+				warn!("Evaluating contracts failed: {}", e);
+			}
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash>,
+		T::AccountId: AsRef<[u8]>,
+	{
+		type Call = Call<T>;
+
+		/// Accept results of evaluated contracts signed by evaluators, one per contract at a time,
+		/// as long as they are due.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (payload, signature) = match call {
+				Call::submit_evaluation { payload, signature } => (payload, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			let evaluator = payload.public.clone().into_account();
+			if !Self::has_permission(&evaluator, Permission::Evaluator) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			let evaluation = match Self::check_evaluation(
+				&payload.contract,
+				payload.evaluated_at,
+				payload.output.len(),
+			) {
+				Ok(evaluation) => evaluation,
+				Err(Error::<T>::EvaluationNotDue) =>
+					if payload.evaluated_at > frame_system::Pallet::<T>::block_number() {
+						return InvalidTransaction::Future.into()
+					} else {
+						return InvalidTransaction::Stale.into()
+					},
+				Err(_) => return InvalidTransaction::Call.into(),
+			};
+
+			ValidTransaction::with_tag_prefix("TemplateEvaluation")
+				.priority(T::EvaluationPriority::get())
+				.and_provides(&payload.contract)
+				.longevity(evaluation.interval.saturated_into::<u64>().max(1))
+				.propagate(true)
+				.build()
+		}
 	}
 
 	#[pallet::call]
@@ -73,7 +913,7 @@ pub mod pallet {
 		T::AccountId: UncheckedFrom<T::Hash>,
 		T::AccountId: AsRef<[u8]>,
 	{
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(Pallet::<T>::call_smart_contract_weight().saturating_add(*gas_limit))]
 		/// A generic extrinsic to wrap
 		/// [pallet_contracts::bare_call](https://github.com/paritytech/substrate/blob/352c46a648a5f2d4526e790a184daa4a1ffdb3bf/frame/contracts/src/lib.rs#L545-L562)
 		///
@@ -84,17 +924,24 @@ pub mod pallet {
 		/// the smart contract itself.
 		/// * `arg` - An argument to be passed to the smart contract.
 		/// * `gas_limit` - The gas limit passed to the contract bare_call. This example should work
-		///   when given a value of around 10000000000
+		///   when given a value of around 10000000000. Only the gas the contract uses is charged.
 		pub fn call_smart_contract(
 			origin: OriginFor<T>,
 			dest: T::AccountId,
 			mut selector: Vec<u8>,
 			arg: u32,
 			#[pallet::compact] gas_limit: Weight,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			let weight = Self::call_smart_contract_weight();
 			// Check against unbounded input
-			ensure!(selector.len() < MAX_LENGTH, Error::<T>::InputTooLarge);
+			ensure!(selector.len() < MAX_LENGTH, Error::<T>::InputTooLarge.with_weight(weight));
+			Self::ensure_not_reentered().map_err(|e| e.with_weight(weight))?;
+			let depth = CallDepth::<T>::get();
+			ensure!(
+				depth < T::MaxCallDepth::get(),
+				Error::<T>::CallDepthExceeded.with_weight(weight)
+			);
 			// Amount to transfer
 			let value: BalanceOf<T> = Default::default();
 			let mut arg_enc: Vec<u8> = arg.encode();
@@ -103,18 +950,13 @@ pub mod pallet {
 			data.append(&mut arg_enc);
 
 			// Do the actual call to the smart contract function
-			pallet_contracts::Pallet::<T>::bare_call(
-				who,
-				dest.clone(),
-				value,
-				gas_limit,
-				data,
-				false,
-			)
-			.result?;
+			let (result, gas_consumed) =
+				Self::call_contract(who, dest.clone(), value, gas_limit, data);
+			let weight = weight.saturating_add(gas_consumed);
+			result.map_err(|e| e.with_weight(weight))?;
 
 			Self::deposit_event(Event::CalledContractFromPallet(dest));
-			Ok(())
+			Ok(Some(weight).into())
 		}
 
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::insert_number(*val))]
@@ -122,10 +964,734 @@ pub mod pallet {
 		/// * `val` - Some integer to be stored.
 		pub fn insert_number(origin: OriginFor<T>, val: u32) -> DispatchResult {
 			ensure_signed(origin)?;
+			Self::ensure_not_reentered()?;
 			// Do something with the value
 			ContractEntry::<T>::put(val);
 			Self::deposit_event(Event::CalledPalletFromContract(val));
 			Ok(())
 		}
+
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::fulfil_request(result.len() as u32)
+				.saturating_add(*gas_limit)
+		)]
+		/// Fulfil request `id` of a contract with `result`, calling the contract back with them.
+		/// Fulfilling a request is free, the contract paid for it, call back included, when
+		/// requesting.
+		///
+		/// * `gas_limit` - At least the gas limit the request was made with, which is what the call
+		///   back is given.
+		pub fn fulfil_request(
+			origin: OriginFor<T>,
+			id: RequestId,
+			result: Vec<u8>,
+			#[pallet::compact] gas_limit: Weight,
+		) -> DispatchResultWithPostInfo {
+			T::FulfilOrigin::ensure_origin(origin)?;
+			ensure!(
+				result.len() <= T::MaxRequestResultLen::get() as usize,
+				Error::<T>::RequestResultTooLarge
+			);
+			let request = Requests::<T>::get(id).ok_or(Error::<T>::UnknownRequest)?;
+			ensure!(gas_limit >= request.gas_limit, Error::<T>::GasLimitTooLow);
+
+			let weight = <T as pallet::Config>::WeightInfo::fulfil_request(result.len() as u32);
+			let gas_consumed = Self::fulfil(id, request, result);
+			Ok((Some(weight.saturating_add(gas_consumed)), Pays::No).into())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::expire_request())]
+		/// Remove request `id`, which was not fulfilled within `RequestTimeout` blocks, returning
+		/// its deposit to the contract. The gas limit the contract paid for is not refunded.
+		pub fn expire_request(origin: OriginFor<T>, id: RequestId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let request = Requests::<T>::get(id).ok_or(Error::<T>::UnknownRequest)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= request.expires_at,
+				Error::<T>::RequestNotExpired
+			);
+			Self::remove_request(id, &request.contract);
+			Self::deposit_event(Event::RequestExpired(id));
+			Ok(())
+		}
+
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::register_evaluation(input.len() as u32)
+		)]
+		/// Have the offchain worker evaluate `contract` every `interval` blocks, calling it with
+		/// `input`, replacing its previous input and interval if already evaluated.
+		pub fn register_evaluation(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			input: Vec<u8>,
+			interval: T::BlockNumber,
+		) -> DispatchResult {
+			T::EvaluationOrigin::ensure_origin(origin)?;
+			ensure!(
+				input.len() <= T::MaxEvaluationInputLen::get() as usize,
+				Error::<T>::EvaluationInputTooLarge
+			);
+			Evaluations::<T>::try_mutate(|evaluations| {
+				evaluations.retain(|e| e.contract != contract);
+				evaluations
+					.try_push(Evaluation { contract: contract.clone(), input, interval })
+					.map_err(|_| Error::<T>::TooManyEvaluations)
+			})?;
+			Self::deposit_event(Event::EvaluationRegistered(contract));
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::deregister_evaluation())]
+		/// Stop evaluating `contract`, removing its latest result.
+		pub fn deregister_evaluation(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+		) -> DispatchResult {
+			T::EvaluationOrigin::ensure_origin(origin)?;
+			Evaluations::<T>::try_mutate(|evaluations| {
+				let len = evaluations.len();
+				evaluations.retain(|e| e.contract != contract);
+				ensure!(evaluations.len() < len, Error::<T>::UnknownEvaluation);
+				Ok::<_, Error<T>>(())
+			})?;
+			EvaluationResults::<T>::remove(&contract);
+			Self::deposit_event(Event::EvaluationDeregistered(contract));
+			Ok(())
+		}
+
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::submit_evaluation(payload.output.len() as u32)
+		)]
+		/// Record the result of an evaluated contract, submitted by the offchain worker as an
+		/// unsigned transaction. The signature of the evaluator is checked by `validate_unsigned`.
+		pub fn submit_evaluation(
+			origin: OriginFor<T>,
+			payload: EvaluationPayloadOf<T>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let EvaluationPayload { contract, evaluated_at, output, .. } = payload;
+			Self::record_evaluation(contract, evaluated_at, output)
+		}
+
+		// Checking the feeder reads its permission, which is not benchmarked
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::feed_value()
+				.saturating_add(T::DbWeight::get().reads(1))
+		)]
+		/// Feed `value` for `key` to the oracle, replacing the value the feeder fed before. The
+		/// aggregated value of the key becomes the median of the values fed for it which are not
+		/// stale.
+		pub fn feed_value(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			value: T::OracleValue,
+		) -> DispatchResult {
+			let feeder = ensure_signed(origin)?;
+			ensure!(
+				Self::has_permission(&feeder, Permission::OracleFeeder),
+				Error::<T>::NotOracleFeeder
+			);
+			Self::feed(feeder, key, value)?;
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::sponsor_calls())]
+		/// Pay the fees of the `call_smart_contract` calls of the message with `selector` of
+		/// `contract`, for up to `quota` calls of each account per `SponsorshipPeriod`. Replaces
+		/// the quota if the sender already sponsors the message, or else reserves
+		/// `SponsorshipDeposit` from the sender.
+		pub fn sponsor_calls(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			selector: [u8; 4],
+			quota: u32,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let key = (contract.clone(), selector);
+			match Sponsorships::<T>::get(&key) {
+				Some(sponsorship) =>
+					ensure!(sponsorship.sponsor == sponsor, Error::<T>::AlreadySponsored),
+				None => <T as Config>::Currency::reserve(&sponsor, T::SponsorshipDeposit::get())
+					.map_err(|_| Error::<T>::InsufficientSponsorshipDeposit)?,
+			}
+			Sponsorships::<T>::insert(&key, Sponsorship { sponsor: sponsor.clone(), quota });
+			Self::deposit_event(Event::CallsSponsored(sponsor, contract, selector));
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_sponsorship())]
+		/// Stop paying the fees of the calls of the message with `selector` of `contract`, getting
+		/// back the deposit of the sponsorship.
+		pub fn withdraw_sponsorship(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			selector: [u8; 4],
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let key = (contract.clone(), selector);
+			let sponsorship = Sponsorships::<T>::get(&key).ok_or(Error::<T>::UnknownSponsorship)?;
+			ensure!(sponsorship.sponsor == sponsor, Error::<T>::NotSponsor);
+			Sponsorships::<T>::remove(&key);
+			<T as Config>::Currency::unreserve(&sponsor, T::SponsorshipDeposit::get());
+			Self::deposit_event(Event::SponsorshipWithdrawn(sponsor, contract, selector));
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::clear_subscriptions())]
+		/// Remove the subscriptions of `contract`, which was terminated, receiving their deposits.
+		pub fn clear_subscriptions(origin: OriginFor<T>, contract: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				pallet_contracts::Pallet::<T>::code_hash(&contract).is_none(),
+				Error::<T>::ContractNotTerminated
+			);
+			ensure!(Self::clear_subscriptions_of(&contract, &who) > 0, Error::<T>::NoSubscriptions);
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::grant_permission())]
+		/// Grant `permission` to `account`.
+		pub fn grant_permission(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			permission: Permission,
+		) -> DispatchResult {
+			T::PermissionOrigin::ensure_origin(origin)?;
+			Permissions::<T>::insert(&account, permission, ());
+			Self::deposit_event(Event::PermissionGranted(account, permission));
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_permission())]
+		/// Revoke `permission` from `account`.
+		pub fn revoke_permission(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			permission: Permission,
+		) -> DispatchResult {
+			T::PermissionOrigin::ensure_origin(origin)?;
+			ensure!(Self::has_permission(&account, permission), Error::<T>::PermissionNotGranted);
+			Permissions::<T>::remove(&account, permission);
+			Self::deposit_event(Event::PermissionRevoked(account, permission));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `permission` was granted to `account`.
+		pub fn has_permission(account: &T::AccountId, permission: Permission) -> bool {
+			Permissions::<T>::contains_key(account, permission)
+		}
+
+		/// Deposit a `ContractSignal` event on behalf of `contract`. Called by the chain extension.
+		pub fn signal_from_contract(
+			contract: T::AccountId,
+			topic: [u8; 32],
+			payload: Vec<u8>,
+		) -> Result<(), Error<T>> {
+			Self::ensure_not_reentered()?;
+			let payload: BoundedVec<u8, T::MaxSignalPayloadLen> =
+				payload.try_into().map_err(|_| Error::<T>::SignalPayloadTooLarge)?;
+			Self::deposit_event(Event::ContractSignal { contract, topic, payload });
+			Ok(())
+		}
+
+		/// Subscribe `contract` to the runtime events matching `filter`, calling its message with
+		/// `selector` with each of them, replacing its selector if already subscribed. Called by
+		/// the chain extension.
+		pub fn subscribe(
+			contract: T::AccountId,
+			filter: T::EventFilter,
+			selector: [u8; 4],
+		) -> Result<(), Error<T>> {
+			Self::ensure_not_reentered()?;
+			Subscriptions::<T>::try_mutate(|subscriptions| {
+				let len = subscriptions.len();
+				subscriptions.retain(|s| s.contract != contract || s.filter != filter);
+				let replaced = subscriptions.len() < len;
+				let count = subscriptions.iter().filter(|s| s.contract == contract).count();
+				ensure!(
+					count < T::MaxSubscriptionsPerContract::get() as usize,
+					Error::<T>::TooManySubscriptions
+				);
+				subscriptions
+					.try_push(Subscription { contract: contract.clone(), filter, selector })
+					.map_err(|_| Error::<T>::TooManySubscriptions)?;
+				if !replaced {
+					<T as Config>::Currency::reserve(&contract, T::SubscriptionDeposit::get())
+						.map_err(|_| Error::<T>::InsufficientSubscriptionDeposit)?;
+				}
+				Ok(())
+			})
+		}
+
+		/// Remove the subscription of `contract` to the runtime events matching `filter`, if any.
+		/// Called by the chain extension.
+		pub fn unsubscribe(contract: T::AccountId, filter: T::EventFilter) -> Result<(), Error<T>> {
+			Self::ensure_not_reentered()?;
+			let removed = Subscriptions::<T>::mutate(|subscriptions| {
+				let len = subscriptions.len();
+				subscriptions.retain(|s| s.contract != contract || s.filter != filter);
+				subscriptions.len() < len
+			});
+			if removed {
+				<T as Config>::Currency::unreserve(&contract, T::SubscriptionDeposit::get());
+			}
+			Ok(())
+		}
+
+		/// Remove the subscriptions of `contract`, which was terminated, paying their deposits to
+		/// `beneficiary`, and return how many were removed.
+		pub(super) fn clear_subscriptions_of(
+			contract: &T::AccountId,
+			beneficiary: &T::AccountId,
+		) -> u32 {
+			let removed = Subscriptions::<T>::mutate(|subscriptions| {
+				let len = subscriptions.len();
+				subscriptions.retain(|s| &s.contract != contract);
+				(len - subscriptions.len()) as u32
+			});
+			if removed > 0 {
+				let deposit = T::SubscriptionDeposit::get().saturating_mul(removed.into());
+				let _ = <T as Config>::Currency::repatriate_reserved(
+					contract,
+					beneficiary,
+					deposit,
+					BalanceStatus::Free,
+				);
+				Self::deposit_event(Event::SubscriptionsCleared(contract.clone(), removed));
+			}
+			removed
+		}
+
+		/// Queue a request of `contract` for `FulfilOrigin` to fulfil, returning its id. The
+		/// contract's message with `selector` is then called back with the id and the result,
+		/// given `gas_limit`. `RequestDeposit` is reserved from the contract until the request is
+		/// fulfilled or expired. Called by the chain extension.
+		pub fn request(
+			contract: T::AccountId,
+			selector: [u8; 4],
+			gas_limit: Weight,
+			payload: Vec<u8>,
+		) -> Result<RequestId, Error<T>> {
+			Self::ensure_not_reentered()?;
+			ensure!(
+				payload.len() <= T::MaxRequestPayloadLen::get() as usize,
+				Error::<T>::RequestPayloadTooLarge
+			);
+			ensure!(gas_limit >= T::MinRequestGasLimit::get(), Error::<T>::RequestGasLimitTooLow);
+			let pending = PendingRequests::<T>::get();
+			ensure!(pending < T::MaxPendingRequests::get(), Error::<T>::TooManyRequests);
+			let pending_of = PendingRequestsOf::<T>::get(&contract);
+			ensure!(pending_of < T::MaxRequestsPerContract::get(), Error::<T>::TooManyRequests);
+			<T as Config>::Currency::reserve(&contract, T::RequestDeposit::get())
+				.map_err(|_| Error::<T>::InsufficientRequestDeposit)?;
+			PendingRequests::<T>::put(pending + 1);
+			PendingRequestsOf::<T>::insert(&contract, pending_of + 1);
+
+			let id = NextRequestId::<T>::mutate(|next| {
+				let id = *next;
+				*next = next.wrapping_add(1);
+				id
+			});
+			let expires_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::RequestTimeout::get());
+			Requests::<T>::insert(
+				id,
+				Request { contract: contract.clone(), selector, gas_limit, payload, expires_at },
+			);
+			Self::deposit_event(Event::RequestQueued(id, contract));
+			Ok(id)
+		}
+
+		/// Remove request `id` of `contract`, returning its deposit.
+		fn remove_request(id: RequestId, contract: &T::AccountId) {
+			Requests::<T>::remove(id);
+			PendingRequests::<T>::mutate(|pending| *pending = pending.saturating_sub(1));
+			PendingRequestsOf::<T>::mutate_exists(contract, |pending| {
+				*pending = pending.map(|n| n.saturating_sub(1)).filter(|n| *n > 0)
+			});
+			<T as Config>::Currency::unreserve(contract, T::RequestDeposit::get());
+		}
+
+		/// The evaluation of `contract`, if an output of `output_len` bytes evaluated at block
+		/// `evaluated_at` is its next result.
+		fn check_evaluation(
+			contract: &T::AccountId,
+			evaluated_at: T::BlockNumber,
+			output_len: usize,
+		) -> Result<EvaluationOf<T>, Error<T>> {
+			let evaluation = Evaluations::<T>::get()
+				.into_inner()
+				.into_iter()
+				.find(|e| &e.contract == contract)
+				.ok_or(Error::<T>::UnknownEvaluation)?;
+			ensure!(
+				output_len <= T::MaxEvaluationOutputLen::get() as usize,
+				Error::<T>::EvaluationOutputTooLarge
+			);
+			ensure!(
+				evaluated_at <= frame_system::Pallet::<T>::block_number() &&
+					Self::is_due(&evaluation, evaluated_at),
+				Error::<T>::EvaluationNotDue
+			);
+			Ok(evaluation)
+		}
+
+		/// Whether `evaluation` is due at block `n`, i.e. its contract has no result from the last
+		/// `interval` blocks.
+		fn is_due(evaluation: &EvaluationOf<T>, n: T::BlockNumber) -> bool {
+			match EvaluationResults::<T>::get(&evaluation.contract) {
+				Some(result) =>
+					n > result.evaluated_at &&
+						n >= result.evaluated_at.saturating_add(evaluation.interval),
+				None => true,
+			}
+		}
+
+		/// Store `output` as the result of `contract` evaluated at block `evaluated_at`.
+		pub(super) fn record_evaluation(
+			contract: T::AccountId,
+			evaluated_at: T::BlockNumber,
+			output: Vec<u8>,
+		) -> DispatchResult {
+			Self::check_evaluation(&contract, evaluated_at, output.len())?;
+			EvaluationResults::<T>::insert(&contract, EvaluationResult { evaluated_at, output });
+			Self::deposit_event(Event::ContractEvaluated(contract));
+			Ok(())
+		}
+
+		/// Feed `value` for `key` to the oracle as `feeder`, without checking it was granted the
+		/// `OracleFeeder` permission, and aggregate the values fed for `key`.
+		pub fn feed(
+			feeder: T::AccountId,
+			key: T::OracleKey,
+			value: T::OracleValue,
+		) -> Result<(), Error<T>> {
+			let now: u64 = T::OracleTime::now().as_millis().saturated_into();
+			let median = FedValues::<T>::try_mutate(key, |fed| {
+				// Stale values and the feeder's previous value make room for the new one
+				fed.retain(|f| {
+					f.feeder != feeder && now.saturating_sub(f.timestamp) < T::OracleMaxAge::get()
+				});
+				fed.try_push(FedValue { feeder, value, timestamp: now })
+					.map_err(|_| Error::<T>::TooManyOracleFeeders)?;
+				let mut values: Vec<_> = fed.iter().map(|f| f.value).collect();
+				values.sort();
+				Ok::<_, Error<T>>(values[values.len() / 2])
+			})?;
+			OracleValues::<T>::insert(key, TimestampedValue { value: median, timestamp: now });
+			Self::deposit_event(Event::OracleUpdated(key, median));
+			Ok(())
+		}
+
+		/// Account paying the fees of `call` signed by `who`: the sponsor of the contract message
+		/// it calls, if it is a sponsored `call_smart_contract` and `who` has calls left in the
+		/// quota of the sponsorship.
+		pub fn sponsor_of(who: &T::AccountId, call: &Call<T>) -> Option<T::AccountId> {
+			let (key, sponsorship) = Self::sponsorship_of(call)?;
+			let (_, calls) = Self::sponsored_calls_in_period(&key, who);
+			(calls < sponsorship.quota).then(|| sponsorship.sponsor)
+		}
+
+		/// Count `call` signed by `who` against the quota of its sponsorship, once the sponsor
+		/// paid its fees.
+		pub fn note_sponsored_call(who: &T::AccountId, call: &Call<T>) {
+			if let Some((key, _)) = Self::sponsorship_of(call) {
+				let (period, calls) = Self::sponsored_calls_in_period(&key, who);
+				SponsoredCalls::<T>::insert(&key, who, (period, calls.saturating_add(1)));
+			}
+		}
+
+		/// The contract message `call` calls and its sponsorship, if it is a sponsored
+		/// `call_smart_contract`.
+		fn sponsorship_of(
+			call: &Call<T>,
+		) -> Option<(SponsorshipKey<T>, Sponsorship<T::AccountId>)> {
+			match call {
+				Call::call_smart_contract { dest, selector, .. } => {
+					let key = (dest.clone(), selector.as_slice().try_into().ok()?);
+					Sponsorships::<T>::get(&key).map(|sponsorship| (key, sponsorship))
+				},
+				_ => None,
+			}
+		}
+
+		/// The current sponsorship period, and the calls `who` made of the contract message `key`
+		/// in it.
+		fn sponsored_calls_in_period(
+			key: &SponsorshipKey<T>,
+			who: &T::AccountId,
+		) -> (T::BlockNumber, u32) {
+			let period = frame_system::Pallet::<T>::block_number() /
+				T::SponsorshipPeriod::get().max(1u32.into());
+			match SponsoredCalls::<T>::get(key, who) {
+				Some((last_period, calls)) if last_period == period => (period, calls),
+				_ => (period, 0),
+			}
+		}
+
+		/// Refuse being called by a contract the pallet is calling, if `Reentrancy` denies it.
+		fn ensure_not_reentered() -> Result<(), Error<T>> {
+			if CallDepth::<T>::get() > 0 && T::Reentrancy::get() == ReentrancyPolicy::Deny {
+				return Err(Error::<T>::ReentrancyDenied)
+			}
+			Ok(())
+		}
+
+		/// Weight of `call_smart_contract` without the call to the contract, including that of
+		/// `ChargeSponsoredTransactionPayment`, which reads the sponsorship of the message and the
+		/// calls the signer made of it, and counts the call if sponsored.
+		pub(super) fn call_smart_contract_weight() -> Weight {
+			T::DbWeight::get()
+				.reads_writes(1, 2)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+				.saturating_add(10_000)
+		}
+	}
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash>,
+		T::AccountId: AsRef<[u8]>,
+	{
+		/// Origin of the calls dispatched by the chain extension on behalf of the contract at
+		/// `address`, if there is one. Contracts being instantiated have none until their
+		/// constructor returns.
+		pub fn contract_origin(address: T::AccountId) -> Option<Origin<T>> {
+			let code_hash = pallet_contracts::Pallet::<T>::code_hash(&address)?;
+			Some(ContractOrigin { address, code_hash })
+		}
+
+		/// Call the contract at `dest` as `origin`, counting the call in `CallDepth` until it
+		/// returns. Returns whether the call succeeded and the gas it consumed.
+		fn call_contract(
+			origin: T::AccountId,
+			dest: T::AccountId,
+			value: BalanceOf<T>,
+			gas_limit: Weight,
+			data: Vec<u8>,
+		) -> (DispatchResult, Weight) {
+			// Restore the depth whether the call succeeds or not
+			let depth = CallDepth::<T>::get();
+			CallDepth::<T>::put(depth + 1);
+			let result = pallet_contracts::Pallet::<T>::bare_call(
+				origin, dest, value, gas_limit, data, false,
+			);
+			if depth == 0 {
+				CallDepth::<T>::kill();
+			} else {
+				CallDepth::<T>::put(depth);
+			}
+			(result.result.map(|_| ()), result.gas_consumed)
+		}
+
+		/// Remove request `id` and call its contract back with the id and `result`, returning the
+		/// gas the call back consumed.
+		pub(super) fn fulfil(id: RequestId, request: RequestOf<T>, result: Vec<u8>) -> Weight {
+			Self::remove_request(id, &request.contract);
+
+			let mut data = request.selector.to_vec();
+			(id, result).encode_to(&mut data);
+			// The contract calls itself, as with deliveries, so it can tell call backs from others
+			let (result, gas_consumed) = Self::call_contract(
+				request.contract.clone(),
+				request.contract,
+				Default::default(),
+				request.gas_limit,
+				data,
+			);
+			match result {
+				Ok(()) => Self::deposit_event(Event::RequestFulfilled(id)),
+				Err(_) => Self::deposit_event(Event::RequestCallbackFailed(id)),
+			}
+			gas_consumed
+		}
+
+		/// Call the contracts due for evaluation at block `n` as read-only queries, as themselves,
+		/// and submit their output as the result of the evaluation. Contracts whose call fails
+		/// or reverts are skipped until the next block.
+		fn evaluate_contracts(n: T::BlockNumber) -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			// Only the nodes of evaluators have a key, the others have nothing to do
+			if !signer.can_sign() {
+				debug!("No local keys of type `tmpl`, not evaluating contracts.");
+				return Ok(())
+			}
+
+			for evaluation in
+				Self::evaluations().into_inner().into_iter().filter(|e| Self::is_due(e, n))
+			{
+				let contract = evaluation.contract;
+				let result = T::Query::execute_query(|| {
+					pallet_contracts::Pallet::<T>::bare_call(
+						contract.clone(),
+						contract.clone(),
+						Default::default(),
+						T::EvaluationGasLimit::get(),
+						evaluation.input,
+						false,
+					)
+				});
+				let output = match result.result {
+					Ok(output) if !output.did_revert() => output.data.0,
+					Ok(_) => {
+						warn!("Evaluating contract {:?} reverted", contract);
+						continue
+					},
+					Err(e) => {
+						warn!("Evaluating contract {:?} failed: {:?}", contract, e);
+						continue
+					},
+				};
+				if output.len() > T::MaxEvaluationOutputLen::get() as usize {
+					warn!("Evaluating contract {:?} returned too long an output", contract);
+					continue
+				}
+
+				let (_, submitted) = signer
+					.send_unsigned_transaction(
+						|account| EvaluationPayload {
+							contract: contract.clone(),
+							evaluated_at: n,
+							output: output.clone(),
+							public: account.public.clone(),
+						},
+						|payload, signature| Call::submit_evaluation { payload, signature },
+					)
+					.ok_or("No local keys to sign the results with.")?;
+				// The other results are still submitted
+				if submitted.is_err() {
+					warn!("Submitting the result of evaluating contract {:?} failed", contract);
+				}
+			}
+			Ok(())
+		}
+
+		/// Queue the first `MaxMatchedEvents` events deposited so far in the block for the
+		/// contracts subscribed to them with the oldest `max_subscriptions` subscriptions,
+		/// returning the weight used.
+		pub(super) fn match_events(max_subscriptions: u32) -> Weight {
+			let mut subscriptions = Subscriptions::<T>::get().into_inner();
+			subscriptions.truncate(max_subscriptions as usize);
+			let (events, deposited) = Self::first_events(T::MaxMatchedEvents::get());
+			let weight = <T as Config>::WeightInfo::match_events(
+				subscriptions.len() as u32,
+				events.len() as u32,
+			);
+
+			let mut queue = DeliveryQueue::<T>::get();
+			let mut dropped = deposited.saturating_sub(events.len() as u32);
+			for record in &events {
+				for subscription in subscriptions.iter().filter(|s| s.filter.matches(&record.event))
+				{
+					let delivery = Delivery {
+						contract: subscription.contract.clone(),
+						selector: subscription.selector,
+						event: record.event.encode(),
+					};
+					if queue.try_push(delivery).is_err() {
+						dropped += 1;
+					}
+				}
+			}
+			DeliveryQueue::<T>::put(queue);
+			if dropped > 0 {
+				Self::deposit_event(Event::DeliveriesDropped(dropped));
+			}
+			weight
+		}
+
+		/// Decode the first `limit` events deposited so far in the block, returning them with the
+		/// number of events deposited. `frame_system::Pallet::events` would decode all of them.
+		fn first_events(
+			limit: u32,
+		) -> (Vec<EventRecord<<T as frame_system::Config>::Event, T::Hash>>, u32) {
+			let pallet = <frame_system::Pallet<T> as PalletInfoAccess>::name();
+			let raw = unhashed::get_raw(&storage_prefix(pallet.as_bytes(), b"Events"))
+				.unwrap_or_default();
+			let input = &mut &raw[..];
+			let deposited = Compact::<u32>::decode(input).map(|count| count.0).unwrap_or_default();
+
+			let mut events = Vec::new();
+			while events.len() < deposited.min(limit) as usize {
+				match EventRecord::decode(input) {
+					Ok(record) => events.push(record),
+					Err(_) => break,
+				}
+			}
+			(events, deposited)
+		}
+
+		/// Call the subscribed contracts with the queued events, oldest first, as long as the
+		/// weight left of `weight_limit` covers `DeliveryGasLimit`, returning the weight used.
+		/// The rest stay queued for the next block. Each contract pays for the gas its delivery
+		/// uses, and deliveries to contracts unable to pay fail. Events for terminated contracts
+		/// are dropped and their subscriptions removed.
+		pub(super) fn deliver_events(weight_limit: Weight) -> Weight {
+			// Reading and writing the queue
+			let mut weight_used = T::DbWeight::get().reads_writes(1, 1);
+			let mut queue = DeliveryQueue::<T>::get().into_inner();
+			let mut delivered = 0;
+			for delivery in &queue {
+				// Finding out whether the contract still exists, and charging its account
+				let delivery_weight = T::DbWeight::get()
+					.reads_writes(2, 1)
+					.saturating_add(T::DeliveryGasLimit::get());
+				if weight_used.saturating_add(delivery_weight) > weight_limit {
+					break
+				}
+				delivered += 1;
+				weight_used = weight_used.saturating_add(T::DbWeight::get().reads(1));
+				if pallet_contracts::Pallet::<T>::code_hash(&delivery.contract).is_none() {
+					// The subscriptions and the contract's account
+					weight_used = weight_used.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+					Self::clear_subscriptions_of(&delivery.contract, &delivery.contract);
+					continue
+				}
+
+				weight_used = weight_used.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				let price = T::DeliveryWeightPrice::convert(T::DeliveryGasLimit::get());
+				let paid = match <T as Config>::Currency::withdraw(
+					&delivery.contract,
+					price,
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				) {
+					Ok(paid) => paid,
+					Err(_) => {
+						Self::deposit_event(Event::DeliveryFailed(delivery.contract.clone()));
+						continue
+					},
+				};
+
+				let mut data = delivery.selector.to_vec();
+				delivery.event.encode_to(&mut data);
+				// The contract calls itself, so it can tell deliveries from other calls
+				let (result, gas_consumed) = Self::call_contract(
+					delivery.contract.clone(),
+					delivery.contract.clone(),
+					Default::default(),
+					T::DeliveryGasLimit::get(),
+					data,
+				);
+				if result.is_err() {
+					Self::deposit_event(Event::DeliveryFailed(delivery.contract.clone()));
+				}
+				weight_used = weight_used.saturating_add(gas_consumed);
+
+				// The price of the gas used is burned
+				let refund = price.saturating_sub(T::DeliveryWeightPrice::convert(gas_consumed));
+				let (refund, _) = paid.split(refund);
+				<T as Config>::Currency::resolve_creating(&delivery.contract, refund);
+			}
+
+			let remaining: BoundedVec<_, T::MaxQueuedDeliveries> = queue
+				.split_off(delivered)
+				.try_into()
+				.expect("what remains of the queue is shorter than it; qed");
+			DeliveryQueue::<T>::put(remaining);
+			weight_used
+		}
 	}
 }
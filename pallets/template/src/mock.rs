@@ -1,38 +1,81 @@
 use crate as pallet_template;
+use crate::{MatchEvent, Permission, ReentrancyPolicy};
+use codec::{Decode, Encode};
 use frame_support::{
 	parameter_types,
+	traits::Get,
 	weights::{IdentityFee, Weight},
 };
 use frame_system;
 use pallet_balances;
-use pallet_contracts::Schedule;
+use pallet_contracts::{
+	chain_extension::{
+		ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+	},
+	Schedule,
+};
 use pallet_randomness_collective_flip;
 use pallet_timestamp;
 use pallet_transaction_payment::CurrencyAdapter;
 use pallet_utility;
+use scale_info::TypeInfo;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
-	AccountId32,
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+	transaction_validity::TransactionPriority,
+	AccountId32, DispatchError, MultiSignature, MultiSigner,
 };
 use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
+/// Transactions submitted by the offchain worker.
+pub type Extrinsic = TestXt<Call, ()>;
 
 pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([2u8; 32]);
+pub const GAS_LIMIT: Weight = 100_000_000_000;
 pub struct ExtBuilder {
 	existential_deposit: u64,
+	reentrancy: ReentrancyPolicy,
+	delivery_weight: Weight,
+	max_matched_events: u32,
+	permissions: Vec<(AccountId32, Permission)>,
 }
 impl Default for ExtBuilder {
 	fn default() -> Self {
-		Self { existential_deposit: 1 }
+		Self {
+			existential_deposit: 1,
+			reentrancy: ReentrancyPolicy::Allow,
+			delivery_weight: 10 * GAS_LIMIT,
+			max_matched_events: 100,
+			permissions: vec![],
+		}
 	}
 }
 impl ExtBuilder {
+	pub fn reentrancy(mut self, reentrancy: ReentrancyPolicy) -> Self {
+		self.reentrancy = reentrancy;
+		self
+	}
+	pub fn delivery_weight(mut self, delivery_weight: Weight) -> Self {
+		self.delivery_weight = delivery_weight;
+		self
+	}
+	pub fn max_matched_events(mut self, max_matched_events: u32) -> Self {
+		self.max_matched_events = max_matched_events;
+		self
+	}
+	pub fn permission(mut self, account: AccountId32, permission: Permission) -> Self {
+		self.permissions.push((account, permission));
+		self
+	}
 	pub fn set_associated_consts(&self) {
 		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		REENTRANCY.with(|v| *v.borrow_mut() = self.reentrancy);
+		DELIVERY_WEIGHT.with(|v| *v.borrow_mut() = self.delivery_weight);
+		MAX_MATCHED_EVENTS.with(|v| *v.borrow_mut() = self.max_matched_events);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		self.set_associated_consts();
@@ -41,6 +84,9 @@ impl ExtBuilder {
 		pallet_balances::GenesisConfig::<Test> { balances: vec![] }
 			.assimilate_storage(&mut t)
 			.unwrap();
+		pallet_template::GenesisConfig::<Test> { permissions: self.permissions }
+			.assimilate_storage(&mut t)
+			.unwrap();
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
@@ -55,7 +101,7 @@ frame_support::construct_runtime!(
 	{
 
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Storage, Config<T>, Event<T>, Origin<T>, ValidateUnsigned},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Randomness: pallet_randomness_collective_flip::{Pallet, Storage},
@@ -146,16 +192,203 @@ impl pallet_contracts::Config for Test {
 	type CallStack = [pallet_contracts::Frame<Self>; 31];
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = ();
-	type ChainExtension = ();
+	type ChainExtension = TestExtension;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = MySchedule;
 }
 
+/// `func_id` of the `TestExtension` function storing a number through `TemplateModule`, as the
+/// caller of the contract.
+pub const STORE_IN_RUNTIME: u32 = 1;
+/// `func_id` of the `TestExtension` function calling the calling contract again through
+/// `TemplateModule::call_smart_contract`, with the same input.
+pub const CALL_SELF: u32 = 1001;
+/// `func_id` of the `TestExtension` function calling the calling contract again through
+/// `TemplateModule::call_smart_contract`, making it store a number.
+pub const CALL_SELF_TO_STORE: u32 = 1002;
+
+/// Input of the `call_chain_extension` fixture making it call `func_id` once, given as the
+/// selector to `TemplateModule::call_smart_contract`. The argument of the call is then the input
+/// of the chain extension function.
+pub fn call_once_selector(func_id: u32) -> Vec<u8> {
+	(func_id, 1u32).encode()
+}
+
+/// Chain extension calling back into `TemplateModule`, so tests can loop from the pallet to a
+/// contract, through the chain extension and back into the pallet. The runtime's own
+/// `ExampleExtension` is tested end to end in the runtime.
+pub struct TestExtension;
+
+impl ChainExtension<Test> for TestExtension {
+	fn call<E: Ext>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Test>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let caller = env.ext().caller().clone();
+		let contract = env.ext().address().clone();
+		let call_self = |func_id: u32| {
+			TemplateModule::call_smart_contract(
+				Origin::signed(contract.clone()),
+				contract.clone(),
+				call_once_selector(func_id),
+				42,
+				GAS_LIMIT,
+			)
+			.map(|_| ())
+			.map_err(|e| e.error)
+		};
+		match func_id {
+			STORE_IN_RUNTIME => {
+				let value: u32 = env.read_as()?;
+				TemplateModule::insert_number(Origin::signed(caller), value)?
+			},
+			CALL_SELF => call_self(CALL_SELF)?,
+			CALL_SELF_TO_STORE => call_self(STORE_IN_RUNTIME)?,
+			_ => return Err(DispatchError::Other("Unimplemented func_id")),
+		}
+		Ok(RetVal::Converging(0))
+	}
+}
+
+thread_local! {
+	static REENTRANCY: RefCell<ReentrancyPolicy> = RefCell::new(ReentrancyPolicy::Allow);
+}
+
+pub struct Reentrancy;
+
+impl Get<ReentrancyPolicy> for Reentrancy {
+	fn get() -> ReentrancyPolicy {
+		REENTRANCY.with(|v| *v.borrow())
+	}
+}
+
+/// Runtime events contracts subscribe to in the tests.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub enum EventFilter {
+	/// Transfers of the native currency to the account.
+	TransferTo(AccountId32),
+	/// Changes of the value stored in `ContractEntry`.
+	ContractEntryChanged,
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_template::BenchmarkHelper<EventFilter> for BenchmarkHelper {
+	fn event_filter() -> EventFilter {
+		EventFilter::ContractEntryChanged
+	}
+}
+
+impl MatchEvent<Event> for EventFilter {
+	fn matches(&self, event: &Event) -> bool {
+		match (self, event) {
+			(
+				EventFilter::TransferTo(account),
+				Event::Balances(pallet_balances::Event::Transfer(_, to, _)),
+			) => to == account,
+			(
+				EventFilter::ContractEntryChanged,
+				Event::TemplateModule(pallet_template::Event::CalledPalletFromContract(_)),
+			) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Prices the weight of deliveries at a thousand units for `GAS_LIMIT`.
+pub struct DeliveryWeightPrice;
+impl Convert<Weight, u64> for DeliveryWeightPrice {
+	fn convert(weight: Weight) -> u64 {
+		weight / (GAS_LIMIT / 1_000)
+	}
+}
+
+parameter_types! {
+	pub const MaxSignalPayloadLen: u32 = 64;
+	pub const MaxCallDepth: u32 = 2;
+	pub const MaxSubscriptions: u32 = 3;
+	pub const MaxSubscriptionsPerContract: u32 = 2;
+	pub const SubscriptionDeposit: u64 = 10;
+	pub static MaxMatchedEvents: u32 = 100;
+	pub const MaxQueuedDeliveries: u32 = 2;
+	pub static DeliveryWeight: Weight = 0;
+	pub const DeliveryGasLimit: Weight = GAS_LIMIT;
+	pub const MaxRequestPayloadLen: u32 = 32;
+	pub const MaxRequestResultLen: u32 = 64;
+	pub const MaxPendingRequests: u32 = 3;
+	pub const MaxRequestsPerContract: u32 = 2;
+	pub const MinRequestGasLimit: Weight = 1_000;
+	pub const RequestDeposit: u64 = 10;
+	pub const RequestTimeout: u64 = 5;
+	pub const MaxEvaluations: u32 = 2;
+	pub const MaxEvaluationInputLen: u32 = 8;
+	pub const MaxEvaluationOutputLen: u32 = 8;
+	pub const EvaluationPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const MaxFeedersPerKey: u32 = 3;
+	pub const OracleMaxAge: u64 = 60_000;
+	pub const SponsorshipPeriod: u64 = 10;
+	pub const SponsorshipDeposit: u64 = 10;
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = ();
+	type MaxSignalPayloadLen = MaxSignalPayloadLen;
+	type MaxCallDepth = MaxCallDepth;
+	type Reentrancy = Reentrancy;
+	type EventFilter = EventFilter;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkHelper;
+	type MaxSubscriptions = MaxSubscriptions;
+	type MaxSubscriptionsPerContract = MaxSubscriptionsPerContract;
+	type SubscriptionDeposit = SubscriptionDeposit;
+	type MaxMatchedEvents = MaxMatchedEvents;
+	type MaxQueuedDeliveries = MaxQueuedDeliveries;
+	type DeliveryWeight = DeliveryWeight;
+	type DeliveryGasLimit = DeliveryGasLimit;
+	type DeliveryWeightPrice = DeliveryWeightPrice;
+	type FulfilOrigin = frame_system::EnsureRoot<AccountId32>;
+	type MaxRequestPayloadLen = MaxRequestPayloadLen;
+	type MaxRequestResultLen = MaxRequestResultLen;
+	type MaxPendingRequests = MaxPendingRequests;
+	type MaxRequestsPerContract = MaxRequestsPerContract;
+	type MinRequestGasLimit = MinRequestGasLimit;
+	type RequestDeposit = RequestDeposit;
+	type RequestTimeout = RequestTimeout;
+	type AuthorityId = pallet_template::crypto::EvaluatorId;
+	type EvaluationOrigin = frame_system::EnsureRoot<AccountId32>;
+	type Query = ();
+	type MaxEvaluations = MaxEvaluations;
+	type MaxEvaluationInputLen = MaxEvaluationInputLen;
+	type MaxEvaluationOutputLen = MaxEvaluationOutputLen;
+	type EvaluationGasLimit = DeliveryGasLimit;
+	type EvaluationPriority = EvaluationPriority;
+	type OracleKey = u32;
+	type OracleValue = u128;
+	type OracleTime = Timestamp;
+	type MaxFeedersPerKey = MaxFeedersPerKey;
+	type OracleMaxAge = OracleMaxAge;
+	type SponsorshipPeriod = SponsorshipPeriod;
+	type SponsorshipDeposit = SponsorshipDeposit;
+	type PermissionOrigin = frame_system::EnsureRoot<AccountId32>;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = MultiSigner;
+	type Signature = MultiSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
 }
 
 parameter_types! {
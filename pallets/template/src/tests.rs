@@ -1,5 +1,93 @@
-use crate::{mock::*, ContractEntry};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	mock::*, CallDepth, ChargeSponsoredTransactionPayment, ContractEntry, ContractOrigin,
+	DeliveryQueue, EnsureContract, EnsureContractWithCodeHash, Error, EvaluationResult,
+	PendingRequests, Permission, ReentrancyPolicy, Requests, Subscription, Subscriptions,
+	TimestampedValue, WeightInfo, KEY_TYPE, SPONSORED_TIP,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_err, assert_noop, assert_ok, parameter_types,
+	traits::{Currency, EnsureOrigin, Get, OffchainWorker, OnFinalize, OnInitialize},
+	unsigned::ValidateUnsigned,
+	weights::{DispatchInfo, Pays},
+};
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_runtime::{
+	traits::{BadOrigin, Convert, Hash, IdentifyAccount, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError},
+	AccountId32, DispatchResult, MultiSigner,
+};
+use sp_std::convert::TryInto;
+use std::sync::Arc;
+
+/// Calls the chain extension function given by the selector, as many times as the argument.
+const CALL_CHAIN_EXTENSION: &[u8] = include_bytes!("../fixtures/call_chain_extension.wasm");
+/// Contract with the `set_value` and `store_in_runtime` messages of the example contract, for
+/// the pallet to call and deliver events to.
+const CONTRACT_WITH_EXTENSION: &[u8] = include_bytes!("../fixtures/contract_with_extension.wasm");
+
+/// Selector of the example contract's `set_value` message.
+const SET_VALUE_SELECTOR: [u8; 4] = [0x00, 0xab, 0xcd, 0xef];
+/// Selector of the example contract's `store_in_runtime` message.
+const STORE_IN_RUNTIME_SELECTOR: [u8; 4] = [0x84, 0xdd, 0x8b, 0x4a];
+
+parameter_types! {
+	pub const Contract: AccountId32 = AccountId32::new([7u8; 32]);
+	pub CodeHash: H256 = H256::repeat_byte(7);
+}
+
+/// Instantiate `wasm` from `ALICE`, returning the address of the contract.
+fn instantiate(wasm: &[u8]) -> AccountId32 {
+	let code_hash = <Test as frame_system::Config>::Hashing::hash(wasm);
+	let _ = Balances::deposit_creating(&ALICE, 1_000_000_000_000);
+	assert_ok!(Contracts::instantiate_with_code(
+		Origin::signed(ALICE),
+		100_000,
+		GAS_LIMIT,
+		wasm.to_vec(),
+		vec![],
+		vec![],
+	));
+	Contracts::contract_address(&ALICE, &code_hash, &[])
+}
+
+/// Instantiate the fixture contract calling the chain extension, returning its address.
+fn instantiate_fixture() -> AccountId32 {
+	instantiate(CALL_CHAIN_EXTENSION)
+}
+
+/// Make `contract` call the `TestExtension` function `func_id` with `input` through
+/// `call_smart_contract`.
+fn call_extension_through_pallet(
+	contract: AccountId32,
+	func_id: u32,
+	input: u32,
+) -> DispatchResult {
+	TemplateModule::call_smart_contract(
+		Origin::signed(ALICE),
+		contract,
+		call_once_selector(func_id),
+		input,
+		GAS_LIMIT,
+	)
+	.map(|_| ())
+	.map_err(|e| e.error)
+}
+
+/// `ResultNum` event of the example contract, as emitted by `contract`.
+fn result_num(contract: AccountId32, number: u32) -> Event {
+	// The index of the event among the contract's events, then its fields
+	let data = (0u8, number).encode();
+	Event::Contracts(pallet_contracts::Event::ContractEmitted { contract, data })
+}
 
 #[test]
 fn stores_value() {
@@ -17,8 +105,853 @@ fn rejects_large_input() {
 	let large_selector = [0; crate::MAX_LENGTH].to_vec();
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			TemplateModule::call_smart_contract(origin, ALICE, large_selector, 5, 100000000),
+			TemplateModule::call_smart_contract(origin, ALICE, large_selector, 5, 100000000)
+				.map_err(|e| e.error),
 			crate::Error::<Test>::InputTooLarge
 		);
 	})
 }
+
+#[test]
+fn deposits_contract_signal() {
+	let topic = [7u8; 32];
+	let payload = vec![1, 2, 3];
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TemplateModule::signal_from_contract(ALICE, topic, payload.clone()));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ContractSignal {
+			contract: ALICE,
+			topic,
+			payload: payload.try_into().unwrap(),
+		}));
+	})
+}
+
+#[test]
+fn rejects_large_signal_payload() {
+	let payload = vec![0; MaxSignalPayloadLen::get() as usize + 1];
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			TemplateModule::signal_from_contract(ALICE, [0; 32], payload),
+			crate::Error::<Test>::SignalPayloadTooLarge
+		);
+	})
+}
+
+#[test]
+fn contract_calls_back_into_pallet() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate_fixture();
+		assert_ok!(call_extension_through_pallet(contract, STORE_IN_RUNTIME, 42));
+		assert_eq!(ContractEntry::<Test>::get(), 42);
+		assert!(!CallDepth::<Test>::exists());
+	})
+}
+
+#[test]
+fn nested_contract_calls_back_into_pallet_within_max_depth() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate_fixture();
+		// pallet -> contract -> extension -> pallet -> contract -> extension -> pallet
+		assert_ok!(call_extension_through_pallet(contract, CALL_SELF_TO_STORE, 0));
+		assert_eq!(ContractEntry::<Test>::get(), 42);
+		assert!(!CallDepth::<Test>::exists());
+	})
+}
+
+#[test]
+fn rejects_nested_contract_calls_beyond_max_depth() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate_fixture();
+		// The contract keeps calling itself through the pallet until the depth is exceeded
+		assert_err!(
+			call_extension_through_pallet(contract, CALL_SELF, 0),
+			Error::<Test>::CallDepthExceeded
+		);
+		assert!(!CallDepth::<Test>::exists());
+	})
+}
+
+#[test]
+fn rejects_contract_calling_back_into_pallet_if_denied() {
+	ExtBuilder::default()
+		.reentrancy(ReentrancyPolicy::Deny)
+		.build()
+		.execute_with(|| {
+			let contract = instantiate_fixture();
+			assert_err!(
+				call_extension_through_pallet(contract.clone(), STORE_IN_RUNTIME, 42),
+				Error::<Test>::ReentrancyDenied
+			);
+			assert_err!(
+				call_extension_through_pallet(contract, CALL_SELF_TO_STORE, 0),
+				Error::<Test>::ReentrancyDenied
+			);
+			assert_eq!(ContractEntry::<Test>::get(), 0);
+			assert!(!CallDepth::<Test>::exists());
+		})
+}
+
+#[test]
+fn stores_value_outside_contract_calls_if_reentrancy_denied() {
+	let origin = Origin::signed(ALICE);
+	ExtBuilder::default()
+		.reentrancy(ReentrancyPolicy::Deny)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TemplateModule::insert_number(origin, 5));
+			assert_eq!(ContractEntry::<Test>::get(), 5);
+		})
+}
+
+#[test]
+fn pallet_calls_contract_message() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		let post_info = TemplateModule::call_smart_contract(
+			Origin::signed(ALICE),
+			contract.clone(),
+			SET_VALUE_SELECTOR.to_vec(),
+			7,
+			GAS_LIMIT,
+		)
+		.unwrap();
+		// Only the gas the contract used is charged
+		assert!(post_info.actual_weight.unwrap() < GAS_LIMIT);
+		// `stored_number` is the first and only field of the contract's storage
+		assert_eq!(Contracts::get_storage(contract.clone(), [0; 32]), Ok(Some(7u32.encode())));
+		System::assert_has_event(result_num(contract.clone(), 7));
+		System::assert_last_event(Event::TemplateModule(crate::Event::CalledContractFromPallet(
+			contract,
+		)));
+	})
+}
+
+#[test]
+fn contract_origin_has_code_hash_of_contract() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate_fixture();
+		let code_hash = <Test as frame_system::Config>::Hashing::hash(CALL_CHAIN_EXTENSION);
+		assert_eq!(
+			TemplateModule::contract_origin(contract.clone()),
+			Some(ContractOrigin { address: contract, code_hash })
+		);
+		assert_eq!(TemplateModule::contract_origin(ALICE), None);
+	})
+}
+
+#[test]
+fn ensure_contract_only_accepts_origin_of_contract() {
+	let origin = |address| Origin::from(ContractOrigin { address, code_hash: H256::zero() });
+	assert_eq!(
+		EnsureContract::<Test, Contract>::try_origin(origin(Contract::get())).ok(),
+		Some(Contract::get())
+	);
+	assert!(EnsureContract::<Test, Contract>::try_origin(origin(ALICE)).is_err());
+	assert!(EnsureContract::<Test, Contract>::try_origin(Origin::signed(Contract::get())).is_err());
+}
+
+#[test]
+fn ensure_contract_with_code_hash_only_accepts_origin_of_contract_code() {
+	let origin = |code_hash| Origin::from(ContractOrigin { address: ALICE, code_hash });
+	assert_eq!(
+		EnsureContractWithCodeHash::<Test, CodeHash>::try_origin(origin(CodeHash::get())).ok(),
+		Some(ALICE)
+	);
+	assert!(EnsureContractWithCodeHash::<Test, CodeHash>::try_origin(origin(H256::zero())).is_err());
+	assert!(
+		EnsureContractWithCodeHash::<Test, CodeHash>::try_origin(Origin::signed(ALICE)).is_err()
+	);
+}
+
+/// Run the hooks matching the events of the block, then delivering them at the start of the
+/// next.
+fn finalize_block() {
+	TemplateModule::on_initialize(1);
+	TemplateModule::on_finalize(1);
+	TemplateModule::on_initialize(2);
+}
+
+/// Fund `accounts` with enough balance to pay the deposits of a few subscriptions.
+fn fund(accounts: &[AccountId32]) {
+	for account in accounts {
+		let _ = Balances::deposit_creating(account, 1_000);
+	}
+}
+
+#[test]
+fn subscriptions_are_bounded_per_contract_and_in_total() {
+	ExtBuilder::default().build().execute_with(|| {
+		fund(&[ALICE, BOB, Contract::get()]);
+		let filter = EventFilter::TransferTo(ALICE);
+		assert_ok!(TemplateModule::subscribe(ALICE, filter.clone(), SET_VALUE_SELECTOR));
+		assert_ok!(TemplateModule::subscribe(ALICE, EventFilter::ContractEntryChanged, [0; 4]));
+		assert_err!(
+			TemplateModule::subscribe(ALICE, EventFilter::TransferTo(BOB), [0; 4]),
+			Error::<Test>::TooManySubscriptions
+		);
+		// Subscribing again replaces the selector
+		assert_ok!(TemplateModule::subscribe(ALICE, filter.clone(), STORE_IN_RUNTIME_SELECTOR));
+		assert_eq!(TemplateModule::subscriptions().len(), 2);
+		assert!(TemplateModule::subscriptions().contains(&Subscription {
+			contract: ALICE,
+			filter: filter.clone(),
+			selector: STORE_IN_RUNTIME_SELECTOR,
+		}));
+
+		assert_ok!(TemplateModule::subscribe(BOB, filter.clone(), [0; 4]));
+		assert_err!(
+			TemplateModule::subscribe(Contract::get(), filter.clone(), [0; 4]),
+			Error::<Test>::TooManySubscriptions
+		);
+		assert_ok!(TemplateModule::unsubscribe(ALICE, filter.clone()));
+		assert_ok!(TemplateModule::subscribe(Contract::get(), filter, [0; 4]));
+	})
+}
+
+#[test]
+fn subscriptions_reserve_a_deposit_returned_when_unsubscribing() {
+	ExtBuilder::default().build().execute_with(|| {
+		let deposit = SubscriptionDeposit::get();
+		let filter = EventFilter::TransferTo(ALICE);
+		let _ = Balances::deposit_creating(&ALICE, deposit);
+		assert_ok!(TemplateModule::subscribe(ALICE, filter.clone(), [0; 4]));
+		assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+		// Replacing the selector reserves nothing more
+		assert_ok!(TemplateModule::subscribe(ALICE, filter.clone(), [1; 4]));
+		assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+		assert_err!(
+			TemplateModule::subscribe(ALICE, EventFilter::ContractEntryChanged, [0; 4]),
+			Error::<Test>::InsufficientSubscriptionDeposit
+		);
+		assert_eq!(TemplateModule::subscriptions().len(), 1);
+
+		assert_ok!(TemplateModule::unsubscribe(ALICE, filter));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(Balances::free_balance(&ALICE), deposit);
+	})
+}
+
+#[test]
+fn subscriptions_of_terminated_contracts_are_cleared() {
+	ExtBuilder::default().build().execute_with(|| {
+		let deposit = SubscriptionDeposit::get();
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		// ALICE and BOB are not contracts, as if they were terminated
+		fund(&[ALICE, BOB, contract.clone()]);
+		for subscriber in [ALICE, BOB, contract.clone()] {
+			assert_ok!(TemplateModule::subscribe(
+				subscriber,
+				EventFilter::ContractEntryChanged,
+				[0; 4]
+			));
+		}
+
+		assert_noop!(
+			TemplateModule::clear_subscriptions(Origin::signed(BOB), contract.clone()),
+			Error::<Test>::ContractNotTerminated
+		);
+		let charlie = AccountId32::new([3u8; 32]);
+		assert_ok!(TemplateModule::clear_subscriptions(Origin::signed(charlie.clone()), BOB));
+		assert_eq!(Balances::free_balance(&charlie), deposit);
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+		assert_noop!(
+			TemplateModule::clear_subscriptions(Origin::signed(charlie), BOB),
+			Error::<Test>::NoSubscriptions
+		);
+
+		// Delivering to ALICE finds out it was terminated, returning the deposit to it
+		assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), 5));
+		finalize_block();
+		System::assert_has_event(Event::TemplateModule(crate::Event::SubscriptionsCleared(
+			ALICE, 1,
+		)));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		let subscribers: Vec<_> =
+			TemplateModule::subscriptions().into_iter().map(|s| s.contract).collect();
+		assert_eq!(subscribers, vec![contract]);
+	})
+}
+
+#[test]
+fn delivers_matching_events_to_subscribed_contract() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		assert_ok!(TemplateModule::subscribe(
+			contract.clone(),
+			EventFilter::ContractEntryChanged,
+			SET_VALUE_SELECTOR
+		));
+
+		assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), 5));
+		finalize_block();
+
+		// `set_value` emits the first four bytes of its argument, the length-prefixed event
+		let event = Event::TemplateModule(crate::Event::CalledPalletFromContract(5));
+		let argument = event.encode().encode();
+		let number = u32::from_le_bytes(argument[..4].try_into().unwrap());
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == result_num(contract.clone(), number)));
+		assert!(DeliveryQueue::<Test>::get().is_empty());
+	})
+}
+
+#[test]
+fn subscribed_contracts_pay_for_the_gas_their_deliveries_use() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		assert_ok!(TemplateModule::subscribe(
+			contract.clone(),
+			EventFilter::ContractEntryChanged,
+			SET_VALUE_SELECTOR
+		));
+		let balance = Balances::free_balance(&contract);
+
+		assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), 5));
+		finalize_block();
+
+		assert!(DeliveryQueue::<Test>::get().is_empty());
+		let paid = balance - Balances::free_balance(&contract);
+		assert!(paid < DeliveryWeightPrice::convert(GAS_LIMIT));
+		assert!(!System::events().iter().any(|record| record.event ==
+			Event::TemplateModule(crate::Event::DeliveryFailed(contract.clone()))));
+	})
+}
+
+#[test]
+fn deliveries_to_contracts_unable_to_pay_for_them_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		assert_ok!(TemplateModule::subscribe(
+			contract.clone(),
+			EventFilter::ContractEntryChanged,
+			SET_VALUE_SELECTOR
+		));
+		let _ = Balances::make_free_balance_be(&contract, 1);
+
+		assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), 5));
+		finalize_block();
+
+		System::assert_has_event(Event::TemplateModule(crate::Event::DeliveryFailed(
+			contract.clone(),
+		)));
+		assert!(DeliveryQueue::<Test>::get().is_empty());
+		assert_eq!(Balances::free_balance(&contract), 1);
+	})
+}
+
+#[test]
+fn undelivered_events_stay_queued_and_overflow_is_dropped() {
+	ExtBuilder::default().delivery_weight(GAS_LIMIT - 1).build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		assert_ok!(TemplateModule::subscribe(
+			contract.clone(),
+			EventFilter::ContractEntryChanged,
+			SET_VALUE_SELECTOR
+		));
+
+		for value in 0..3 {
+			assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), value));
+		}
+		finalize_block();
+
+		assert_eq!(DeliveryQueue::<Test>::get().len(), 2);
+		System::assert_last_event(Event::TemplateModule(crate::Event::DeliveriesDropped(1)));
+	})
+}
+
+#[test]
+fn events_past_max_matched_events_are_dropped() {
+	ExtBuilder::default().max_matched_events(1).build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		assert_ok!(TemplateModule::subscribe(
+			contract,
+			EventFilter::ContractEntryChanged,
+			SET_VALUE_SELECTOR
+		));
+
+		System::reset_events();
+		for value in 0..3 {
+			assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), value));
+		}
+		TemplateModule::on_initialize(1);
+		TemplateModule::on_finalize(1);
+
+		assert_eq!(DeliveryQueue::<Test>::get().len(), 1);
+		System::assert_last_event(Event::TemplateModule(crate::Event::DeliveriesDropped(2)));
+	})
+}
+
+#[test]
+fn only_matching_is_reserved_in_blocks_without_queued_deliveries() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		fund(&[contract.clone()]);
+		assert_ok!(TemplateModule::subscribe(
+			contract,
+			EventFilter::ContractEntryChanged,
+			SET_VALUE_SELECTOR
+		));
+
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		assert_eq!(
+			TemplateModule::on_initialize(1),
+			db_weight.reads(2) +
+				db_weight.reads_writes(1, 2) +
+				<() as WeightInfo>::match_events(1, MaxMatchedEvents::get())
+		);
+	})
+}
+
+#[test]
+fn blocks_without_subscriptions_only_check_for_them() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TemplateModule::insert_number(Origin::signed(ALICE), 5));
+		assert_eq!(
+			TemplateModule::on_initialize(1),
+			<Test as frame_system::Config>::DbWeight::get().reads(2)
+		);
+		TemplateModule::on_finalize(1);
+		assert!(DeliveryQueue::<Test>::get().is_empty());
+		assert!(Subscriptions::<Test>::get().is_empty());
+	})
+}
+
+#[test]
+fn requests_are_bounded_and_only_fulfilled_by_fulfil_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		fund(&[ALICE, BOB, Contract::get()]);
+		assert_err!(
+			TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![0; 33]),
+			Error::<Test>::RequestPayloadTooLarge
+		);
+		assert_err!(
+			TemplateModule::request(ALICE, [0; 4], MinRequestGasLimit::get() - 1, vec![]),
+			Error::<Test>::RequestGasLimitTooLow
+		);
+		assert_eq!(TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![0; 32]), Ok(0));
+		assert_eq!(TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![]), Ok(1));
+		assert_err!(
+			TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![]),
+			Error::<Test>::TooManyRequests
+		);
+		assert_eq!(TemplateModule::request(BOB, [0; 4], GAS_LIMIT, vec![]), Ok(2));
+		assert_err!(
+			TemplateModule::request(Contract::get(), [0; 4], GAS_LIMIT, vec![]),
+			Error::<Test>::TooManyRequests
+		);
+
+		assert_noop!(
+			TemplateModule::fulfil_request(Origin::signed(ALICE), 0, vec![], GAS_LIMIT),
+			BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::fulfil_request(Origin::root(), 0, vec![0; 65], GAS_LIMIT),
+			Error::<Test>::RequestResultTooLarge
+		);
+		assert_noop!(
+			TemplateModule::fulfil_request(Origin::root(), 0, vec![], GAS_LIMIT - 1),
+			Error::<Test>::GasLimitTooLow
+		);
+		assert_noop!(
+			TemplateModule::fulfil_request(Origin::root(), 3, vec![], GAS_LIMIT),
+			Error::<Test>::UnknownRequest
+		);
+
+		// `ALICE` is no contract, the request is fulfilled all the same
+		assert_ok!(TemplateModule::fulfil_request(Origin::root(), 0, vec![], GAS_LIMIT));
+		System::assert_last_event(Event::TemplateModule(crate::Event::RequestCallbackFailed(0)));
+		assert_noop!(
+			TemplateModule::fulfil_request(Origin::root(), 0, vec![], GAS_LIMIT),
+			Error::<Test>::UnknownRequest
+		);
+		assert_eq!(TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![]), Ok(3));
+	})
+}
+
+#[test]
+fn fulfilled_request_calls_contract_back_with_id_and_result() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		for _ in 0..2 {
+			assert_ok!(TemplateModule::request(
+				contract.clone(),
+				SET_VALUE_SELECTOR,
+				GAS_LIMIT,
+				vec![]
+			));
+		}
+
+		// The contract paid for the call back when requesting
+		let post_info = TemplateModule::fulfil_request(Origin::root(), 1, vec![7], GAS_LIMIT);
+		assert_eq!(post_info.map(|info| info.pays_fee), Ok(Pays::No));
+
+		// `set_value` stores the first four bytes of the id
+		assert_eq!(Contracts::get_storage(contract.clone(), [0; 32]), Ok(Some(1u32.encode())));
+		System::assert_has_event(result_num(contract, 1));
+		System::assert_last_event(Event::TemplateModule(crate::Event::RequestFulfilled(1)));
+		assert!(Requests::<Test>::get(1).is_none());
+		assert_eq!(PendingRequests::<Test>::get(), 1);
+	})
+}
+
+#[test]
+fn requests_reserve_a_deposit_returned_when_fulfilled_or_expired() {
+	ExtBuilder::default().build().execute_with(|| {
+		let deposit = RequestDeposit::get();
+		let _ = Balances::deposit_creating(&ALICE, deposit);
+		assert_ok!(TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![]));
+		assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+		assert_err!(
+			TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![]),
+			Error::<Test>::InsufficientRequestDeposit
+		);
+		assert_eq!(PendingRequests::<Test>::get(), 1);
+		assert_ok!(TemplateModule::fulfil_request(Origin::root(), 0, vec![], GAS_LIMIT));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+
+		assert_ok!(TemplateModule::request(ALICE, [0; 4], GAS_LIMIT, vec![]));
+		let expires_at = System::block_number() + RequestTimeout::get();
+		System::set_block_number(expires_at - 1);
+		assert_noop!(
+			TemplateModule::expire_request(Origin::signed(BOB), 1),
+			Error::<Test>::RequestNotExpired
+		);
+		System::set_block_number(expires_at);
+		assert_ok!(TemplateModule::expire_request(Origin::signed(BOB), 1));
+		System::assert_last_event(Event::TemplateModule(crate::Event::RequestExpired(1)));
+		assert_eq!(Balances::free_balance(&ALICE), deposit);
+		assert!(Requests::<Test>::get(1).is_none());
+		assert_eq!(PendingRequests::<Test>::get(), 0);
+		assert_noop!(
+			TemplateModule::expire_request(Origin::signed(BOB), 1),
+			Error::<Test>::UnknownRequest
+		);
+	})
+}
+
+#[test]
+fn evaluations_are_bounded_and_registered_by_evaluation_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			TemplateModule::register_evaluation(Origin::signed(ALICE), ALICE, vec![], 1),
+			BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::register_evaluation(Origin::root(), ALICE, vec![0; 9], 1),
+			Error::<Test>::EvaluationInputTooLarge
+		);
+		assert_ok!(TemplateModule::register_evaluation(Origin::root(), ALICE, vec![], 1));
+		assert_ok!(TemplateModule::register_evaluation(Origin::root(), BOB, vec![], 1));
+		assert_noop!(
+			TemplateModule::register_evaluation(Origin::root(), Contract::get(), vec![], 1),
+			Error::<Test>::TooManyEvaluations
+		);
+		// Registering again replaces the input and interval
+		assert_ok!(TemplateModule::register_evaluation(Origin::root(), ALICE, vec![1], 5));
+		assert_eq!(TemplateModule::evaluations().len(), 2);
+
+		assert_ok!(TemplateModule::deregister_evaluation(Origin::root(), ALICE));
+		assert_noop!(
+			TemplateModule::deregister_evaluation(Origin::root(), ALICE),
+			Error::<Test>::UnknownEvaluation
+		);
+		assert_ok!(TemplateModule::register_evaluation(Origin::root(), Contract::get(), vec![], 1));
+	})
+}
+
+#[test]
+fn permissions_are_granted_at_genesis_and_by_permission_origin() {
+	let governance = Permission::GovernanceContract;
+	ExtBuilder::default().permission(ALICE, governance).build().execute_with(|| {
+		assert!(TemplateModule::has_permission(&ALICE, governance));
+		assert!(!TemplateModule::has_permission(&BOB, governance));
+
+		assert_noop!(
+			TemplateModule::grant_permission(Origin::signed(ALICE), BOB, governance),
+			BadOrigin
+		);
+		assert_ok!(TemplateModule::grant_permission(Origin::root(), BOB, governance));
+		assert!(TemplateModule::has_permission(&BOB, governance));
+
+		assert_noop!(
+			TemplateModule::revoke_permission(Origin::signed(ALICE), BOB, governance),
+			BadOrigin
+		);
+		assert_ok!(TemplateModule::revoke_permission(Origin::root(), BOB, governance));
+		assert!(!TemplateModule::has_permission(&BOB, governance));
+		assert_noop!(
+			TemplateModule::revoke_permission(Origin::root(), BOB, governance),
+			Error::<Test>::PermissionNotGranted
+		);
+	})
+}
+
+/// Externalities of `ExtBuilder` with an offchain worker, its transaction pool and a key of the
+/// offchain worker, returning the pool's state and the account of the key.
+fn offchain_ext() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>, AccountId32) {
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	let public = SyncCryptoStore::sr25519_generate_new(&keystore, KEY_TYPE, None).unwrap();
+
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+	(ext, pool_state, MultiSigner::from(public).into_account())
+}
+
+#[test]
+fn offchain_worker_without_a_key_submits_nothing() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+	ext.execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		assert_ok!(TemplateModule::register_evaluation(Origin::root(), contract, vec![], 1));
+
+		TemplateModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+	})
+}
+
+#[test]
+fn offchain_worker_submits_results_of_due_evaluations() {
+	let (mut ext, pool_state, evaluator) = offchain_ext();
+	ext.execute_with(|| {
+		let contract = instantiate(CONTRACT_WITH_EXTENSION);
+		let mut input = SET_VALUE_SELECTOR.to_vec();
+		input.extend(7u32.encode());
+		assert_ok!(TemplateModule::register_evaluation(Origin::root(), contract.clone(), input, 2));
+
+		TemplateModule::offchain_worker(1);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &tx[..]).unwrap();
+		assert_eq!(tx.signature, None);
+		let call = match tx.call {
+			Call::TemplateModule(call) => call,
+			call => panic!("unexpected call {:?}", call),
+		};
+		let (payload, signature) = match call.clone() {
+			crate::Call::submit_evaluation { payload, signature } => (payload, signature),
+			call => panic!("unexpected call {:?}", call),
+		};
+		// `set_value` returns `Ok(())`
+		assert_eq!((payload.evaluated_at, &payload.output[..]), (1, &[0][..]));
+
+		let validate = |call| TemplateModule::validate_unsigned(TransactionSource::Local, call);
+		assert_eq!(validate(&call), InvalidTransaction::BadSigner.into());
+		assert_ok!(TemplateModule::grant_permission(
+			Origin::root(),
+			evaluator,
+			Permission::Evaluator
+		));
+		let mut forged = payload.clone();
+		forged.output = vec![1];
+		let forged =
+			crate::Call::submit_evaluation { payload: forged, signature: signature.clone() };
+		assert_eq!(validate(&forged), InvalidTransaction::BadProof.into());
+		assert!(validate(&call).is_ok());
+
+		assert_ok!(TemplateModule::submit_evaluation(Origin::none(), payload, signature));
+		assert_eq!(
+			TemplateModule::evaluation_results(&contract),
+			Some(EvaluationResult { evaluated_at: 1, output: vec![0] })
+		);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ContractEvaluated(contract)));
+
+		// Not due again until its interval passed
+		assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+		TemplateModule::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+	})
+}
+
+#[test]
+fn oracle_aggregates_median_of_fresh_values_fed_by_feeders() {
+	ExtBuilder::default().build().execute_with(|| {
+		let charlie = AccountId32::new([3u8; 32]);
+		assert_noop!(
+			TemplateModule::feed_value(Origin::signed(ALICE), 1, 10),
+			Error::<Test>::NotOracleFeeder
+		);
+		for feeder in [ALICE, BOB, charlie.clone()] {
+			assert_ok!(TemplateModule::grant_permission(
+				Origin::root(),
+				feeder,
+				Permission::OracleFeeder
+			));
+		}
+
+		Timestamp::set_timestamp(1_000);
+		assert_ok!(TemplateModule::feed_value(Origin::signed(ALICE), 1, 10));
+		assert_ok!(TemplateModule::feed_value(Origin::signed(BOB), 1, 30));
+		assert_ok!(TemplateModule::feed_value(Origin::signed(charlie.clone()), 1, 20));
+		assert_eq!(
+			TemplateModule::oracle_value(1),
+			Some(TimestampedValue { value: 20, timestamp: 1_000 })
+		);
+		System::assert_last_event(Event::TemplateModule(crate::Event::OracleUpdated(1, 20)));
+		assert_eq!(TemplateModule::oracle_value(2), None);
+
+		// Feeding again replaces the value of the feeder
+		assert_ok!(TemplateModule::feed_value(Origin::signed(charlie.clone()), 1, 40));
+		assert_eq!(TemplateModule::oracle_value(1).unwrap().value, 30);
+		assert_noop!(
+			TemplateModule::feed(AccountId32::new([4u8; 32]), 1, 0),
+			Error::<Test>::TooManyOracleFeeders
+		);
+
+		// Stale values are no longer aggregated, and make room for other feeders
+		Timestamp::set_timestamp(1_000 + OracleMaxAge::get());
+		assert_ok!(TemplateModule::feed_value(Origin::signed(ALICE), 1, 50));
+		assert_eq!(
+			TemplateModule::oracle_value(1),
+			Some(TimestampedValue { value: 50, timestamp: 61_000 })
+		);
+		assert_eq!(TemplateModule::fed_values(1).len(), 1);
+		assert_ok!(TemplateModule::feed(AccountId32::new([4u8; 32]), 1, 0));
+	})
+}
+
+#[test]
+fn sponsors_pay_fees_of_sponsored_calls_within_quota() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = AccountId32::new([3u8; 32]);
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let _ = Balances::deposit_creating(&BOB, 1_000_000);
+		assert_ok!(TemplateModule::sponsor_calls(
+			Origin::signed(ALICE),
+			contract.clone(),
+			[1u8; 4],
+			1
+		));
+		System::assert_last_event(Event::TemplateModule(crate::Event::CallsSponsored(
+			ALICE,
+			contract.clone(),
+			[1u8; 4],
+		)));
+		assert_noop!(
+			TemplateModule::sponsor_calls(Origin::signed(BOB), contract.clone(), [1u8; 4], 5),
+			Error::<Test>::AlreadySponsored
+		);
+		assert_eq!(Balances::reserved_balance(&ALICE), SponsorshipDeposit::get());
+		let funds = 1_000_000 - SponsorshipDeposit::get();
+
+		let call = |selector: [u8; 4]| {
+			Call::TemplateModule(crate::Call::call_smart_contract {
+				dest: contract.clone(),
+				selector: selector.to_vec(),
+				arg: 0,
+				gas_limit: 0,
+			})
+		};
+		let info = DispatchInfo { weight: 100, ..Default::default() };
+		// Free balances of the sponsor and the signer after charging the fees of `call`
+		let charge = |call: &Call| {
+			ChargeSponsoredTransactionPayment::<Test>::from(0)
+				.pre_dispatch(&BOB, call, &info, 0)
+				.unwrap();
+			(Balances::free_balance(&ALICE), Balances::free_balance(&BOB))
+		};
+
+		let (sponsor, signer) = charge(&call([1u8; 4]));
+		assert!(sponsor < funds);
+		assert_eq!(signer, 1_000_000);
+		assert_eq!(
+			TemplateModule::sponsored_calls((contract.clone(), [1u8; 4]), BOB),
+			Some((0, 1))
+		);
+		// Beyond the quota, and for messages not sponsored, the signer pays
+		assert_eq!(charge(&call([1u8; 4])), (sponsor, signer - (funds - sponsor)));
+		let (_, signer) = charge(&call([2u8; 4]));
+		// The quota is renewed every period
+		System::set_block_number(SponsorshipPeriod::get());
+		assert_eq!(charge(&call([1u8; 4])).1, signer);
+
+		assert_noop!(
+			TemplateModule::withdraw_sponsorship(Origin::signed(BOB), contract.clone(), [1u8; 4]),
+			Error::<Test>::NotSponsor
+		);
+		assert_ok!(TemplateModule::withdraw_sponsorship(
+			Origin::signed(ALICE),
+			contract.clone(),
+			[1u8; 4]
+		));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_noop!(
+			TemplateModule::withdraw_sponsorship(Origin::signed(ALICE), contract.clone(), [1u8; 4]),
+			Error::<Test>::UnknownSponsorship
+		);
+		assert!(charge(&call([1u8; 4])).1 < signer);
+	})
+}
+
+#[test]
+fn sponsorships_reserve_a_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = AccountId32::new([3u8; 32]);
+		let deposit = SponsorshipDeposit::get();
+		let _ = Balances::deposit_creating(&ALICE, deposit);
+		assert_ok!(TemplateModule::sponsor_calls(
+			Origin::signed(ALICE),
+			contract.clone(),
+			[1u8; 4],
+			1
+		));
+		// Replacing the quota reserves nothing more
+		assert_ok!(TemplateModule::sponsor_calls(
+			Origin::signed(ALICE),
+			contract.clone(),
+			[1u8; 4],
+			2
+		));
+		assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+		assert_noop!(
+			TemplateModule::sponsor_calls(Origin::signed(ALICE), contract, [2u8; 4], 1),
+			Error::<Test>::InsufficientSponsorshipDeposit
+		);
+	})
+}
+
+#[test]
+fn sponsored_calls_with_a_tip_are_refused() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = AccountId32::new([3u8; 32]);
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let _ = Balances::deposit_creating(&BOB, 1_000_000);
+		assert_ok!(TemplateModule::sponsor_calls(
+			Origin::signed(ALICE),
+			contract.clone(),
+			[1u8; 4],
+			1
+		));
+		let call = |selector: [u8; 4]| {
+			Call::TemplateModule(crate::Call::call_smart_contract {
+				dest: contract.clone(),
+				selector: selector.to_vec(),
+				arg: 0,
+				gas_limit: 0,
+			})
+		};
+		let info = DispatchInfo { weight: 100, ..Default::default() };
+		let tipped = || ChargeSponsoredTransactionPayment::<Test>::from(1);
+
+		let refused: TransactionValidityError = InvalidTransaction::Custom(SPONSORED_TIP).into();
+		assert_eq!(tipped().validate(&BOB, &call([1u8; 4]), &info, 0), Err(refused));
+		assert_eq!(
+			tipped().pre_dispatch(&BOB, &call([1u8; 4]), &info, 0).map(|_| ()),
+			Err(refused)
+		);
+		assert_eq!(Balances::free_balance(&BOB), 1_000_000);
+		assert_eq!(TemplateModule::sponsored_calls((contract.clone(), [1u8; 4]), BOB), None);
+
+		// Calls the signer pays for may tip
+		assert_ok!(tipped().pre_dispatch(&BOB, &call([2u8; 4]), &info, 0));
+		assert!(Balances::free_balance(&BOB) < 1_000_000);
+	})
+}
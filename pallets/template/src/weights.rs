@@ -1,73 +1,206 @@
-// This file is part of Substrate.
-
-// Copyright (C) 2021 Parity Technologies (UK) Ltd.
-// SPDX-License-Identifier: Apache-2.0
-
-// Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License.
-// You may obtain a copy of the License at
-//
-// http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS,
-// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// See the License for the specific language governing permissions and
-// limitations under the License.
-
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_template.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-12-06, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
-
-// Executed Command:
-
-// ./target/release/node-template
-// benchmark
-// --chain
-// dev
-// --pallet=pallet_template
-// --extrinsic=*
-// --repeat=20
-// --steps=50
-// --execution
-// wasm
-// --wasm-execution
-// compiled
-// --raw
-// --output
-// pallets/template/src/weights.rs
-// --template=./weight-template.hbs
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-
-use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+//! `insert_number` comes from the template's benchmark results (2021-12-06, dev chain, compiled
+//! Wasm), plus the `CallDepth` read it makes since. Every other weight is a hand-written estimate,
+//! not a benchmark result: `benchmarking.rs` benchmarks them all, but has not been run on reference
+//! hardware yet. Do not rely on them in production until this file is replaced by the benchmarks'
+//! output (see the README).
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
 use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_template.
 pub trait WeightInfo {
-	fn insert_number(s: u32, ) -> Weight;
-	}
+	fn insert_number(s: u32) -> Weight;
+	fn match_events(s: u32, e: u32) -> Weight;
+	fn fulfil_request(n: u32) -> Weight;
+	fn expire_request() -> Weight;
+	fn register_evaluation(n: u32) -> Weight;
+	fn deregister_evaluation() -> Weight;
+	fn submit_evaluation(n: u32) -> Weight;
+	fn feed_value() -> Weight;
+	fn sponsor_calls() -> Weight;
+	fn withdraw_sponsorship() -> Weight;
+	fn clear_subscriptions() -> Weight;
+	fn grant_permission() -> Weight;
+	fn revoke_permission() -> Weight;
+}
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Execution weight of `insert_number`, which reads `CallDepth` and writes `ContractEntry`.
+const INSERT_NUMBER: Weight = 18_333_000;
+/// Execution weight of `match_events`, which reads `Subscriptions` and `System::Events`, and reads
+/// and writes `DeliveryQueue`.
+const MATCH_EVENTS: Weight = 4_500_000;
+/// Execution weight of matching the events against each subscription.
+const MATCH_EVENTS_PER_SUBSCRIPTION: Weight = 9_000_000;
+/// Execution weight of matching each event against the subscriptions.
+const MATCH_EVENTS_PER_EVENT: Weight = 9_300_000;
+/// Execution weight of `fulfil_request` without the call back, which reads and writes `Requests`,
+/// `PendingRequests`, `PendingRequestsOf`, `System::Account` and `CallDepth`, and reads
+/// `Contracts::ContractInfoOf`.
+const FULFIL_REQUEST: Weight = 42_000_000;
+/// Execution weight of each byte of the result passed to `fulfil_request`.
+const FULFIL_REQUEST_PER_BYTE: Weight = 2_000;
+/// Execution weight of `expire_request`, which reads and writes `Requests`, `PendingRequests`,
+/// `PendingRequestsOf` and `System::Account`, and reads `System::Number`.
+const EXPIRE_REQUEST: Weight = 32_000_000;
+/// Execution weight of `register_evaluation`, which reads and writes `Evaluations`.
+const REGISTER_EVALUATION: Weight = 24_000_000;
+/// Execution weight of each byte of the input passed to `register_evaluation`.
+const REGISTER_EVALUATION_PER_BYTE: Weight = 1_000;
+/// Execution weight of `deregister_evaluation`, which reads and writes `Evaluations`, and writes
+/// `EvaluationResults`.
+const DEREGISTER_EVALUATION: Weight = 26_000_000;
+/// Execution weight of `submit_evaluation`, which reads `Evaluations` and `System::Number`, and
+/// reads and writes `EvaluationResults`.
+const SUBMIT_EVALUATION: Weight = 28_000_000;
+/// Execution weight of each byte of the result passed to `submit_evaluation`.
+const SUBMIT_EVALUATION_PER_BYTE: Weight = 2_000;
+/// Execution weight of `feed_value`, which reads `Timestamp::Now`, reads and writes `FedValues`,
+/// and writes `OracleValues`.
+const FEED_VALUE: Weight = 38_000_000;
+/// Execution weight of `sponsor_calls`, which reads and writes `Sponsorships` and
+/// `System::Account`.
+const SPONSOR_CALLS: Weight = 36_000_000;
+/// Execution weight of `withdraw_sponsorship`, which reads and writes `Sponsorships` and
+/// `System::Account`.
+const WITHDRAW_SPONSORSHIP: Weight = 35_000_000;
+/// Execution weight of `clear_subscriptions`, which reads `Contracts::ContractInfoOf`, reads and
+/// writes `Subscriptions`, and the `System::Account` of the contract and of the beneficiary.
+const CLEAR_SUBSCRIPTIONS: Weight = 45_000_000;
+/// Execution weight of `grant_permission`, which writes `Permissions`.
+const GRANT_PERMISSION: Weight = 15_000_000;
+/// Execution weight of `revoke_permission`, which reads and writes `Permissions`.
+const REVOKE_PERMISSION: Weight = 18_000_000;
+
+/// Weights for pallet_template, from the estimates above and the runtime's database weights.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn insert_number(_s: u32) -> Weight {
+		INSERT_NUMBER.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	fn match_events(s: u32, e: u32) -> Weight {
+		MATCH_EVENTS
+			.saturating_add(MATCH_EVENTS_PER_SUBSCRIPTION.saturating_mul(s.into()))
+			.saturating_add(MATCH_EVENTS_PER_EVENT.saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads_writes(3, 1))
+	}
+
+	fn fulfil_request(n: u32) -> Weight {
+		FULFIL_REQUEST
+			.saturating_add(FULFIL_REQUEST_PER_BYTE.saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads_writes(6, 5))
+	}
+
+	fn expire_request() -> Weight {
+		EXPIRE_REQUEST.saturating_add(T::DbWeight::get().reads_writes(5, 4))
+	}
+
+	fn register_evaluation(n: u32) -> Weight {
+		REGISTER_EVALUATION
+			.saturating_add(REGISTER_EVALUATION_PER_BYTE.saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	fn deregister_evaluation() -> Weight {
+		DEREGISTER_EVALUATION.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+
+	fn submit_evaluation(n: u32) -> Weight {
+		SUBMIT_EVALUATION
+			.saturating_add(SUBMIT_EVALUATION_PER_BYTE.saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads_writes(3, 1))
+	}
+
+	fn feed_value() -> Weight {
+		FEED_VALUE.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	fn sponsor_calls() -> Weight {
+		SPONSOR_CALLS.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	fn withdraw_sponsorship() -> Weight {
+		WITHDRAW_SPONSORSHIP.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	fn clear_subscriptions() -> Weight {
+		CLEAR_SUBSCRIPTIONS.saturating_add(T::DbWeight::get().reads_writes(4, 3))
+	}
+
+	fn grant_permission() -> Weight {
+		GRANT_PERMISSION.saturating_add(T::DbWeight::get().writes(1))
+	}
 
-	// Storage: TemplateModule ContractEntry (r:0 w:1)
-	fn insert_number(_s: u32, ) -> Weight {
-		(18_333_000 as Weight)
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-			}
+	fn revoke_permission() -> Weight {
+		REVOKE_PERMISSION.saturating_add(T::DbWeight::get().reads_writes(1, 1))
 	}
+}
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	// Storage: TemplateModule ContractEntry (r:0 w:1)
-	fn insert_number(_s: u32, ) -> Weight {
-		(18_333_000 as Weight)
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-			}
-	}
\ No newline at end of file
+	fn insert_number(_s: u32) -> Weight {
+		INSERT_NUMBER.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+
+	fn match_events(s: u32, e: u32) -> Weight {
+		MATCH_EVENTS
+			.saturating_add(MATCH_EVENTS_PER_SUBSCRIPTION.saturating_mul(s.into()))
+			.saturating_add(MATCH_EVENTS_PER_EVENT.saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+	}
+
+	fn fulfil_request(n: u32) -> Weight {
+		FULFIL_REQUEST
+			.saturating_add(FULFIL_REQUEST_PER_BYTE.saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads_writes(6, 5))
+	}
+
+	fn expire_request() -> Weight {
+		EXPIRE_REQUEST.saturating_add(RocksDbWeight::get().reads_writes(5, 4))
+	}
+
+	fn register_evaluation(n: u32) -> Weight {
+		REGISTER_EVALUATION
+			.saturating_add(REGISTER_EVALUATION_PER_BYTE.saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+
+	fn deregister_evaluation() -> Weight {
+		DEREGISTER_EVALUATION.saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+
+	fn submit_evaluation(n: u32) -> Weight {
+		SUBMIT_EVALUATION
+			.saturating_add(SUBMIT_EVALUATION_PER_BYTE.saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+	}
+
+	fn feed_value() -> Weight {
+		FEED_VALUE.saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+
+	fn sponsor_calls() -> Weight {
+		SPONSOR_CALLS.saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+
+	fn withdraw_sponsorship() -> Weight {
+		WITHDRAW_SPONSORSHIP.saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+
+	fn clear_subscriptions() -> Weight {
+		CLEAR_SUBSCRIPTIONS.saturating_add(RocksDbWeight::get().reads_writes(4, 3))
+	}
+
+	fn grant_permission() -> Weight {
+		GRANT_PERMISSION.saturating_add(RocksDbWeight::get().writes(1))
+	}
+
+	fn revoke_permission() -> Weight {
+		REVOKE_PERMISSION.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+}
@@ -0,0 +1,106 @@
+//! Calls the chain extension with arbitrary input, as a contract could, checking that it never
+//! panics, charges at least the database writes of every storage item it changes, and always
+//! returns a status code listed in `RetCode::metadata` or an error trapping the contract.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use node_template_runtime::{
+	chain_extension::{
+		extension_metadata, read_only,
+		testing::{new_test_ext, MockEnvironment},
+		RetCode,
+	},
+	AccountId, RocksDbWeight, Runtime,
+};
+use sp_io::hashing::twox_128;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Arbitrary)]
+struct Call {
+	/// Calls the registered function at this index, modulo their number, if any.
+	function: Option<u16>,
+	/// Called instead of a registered function, mostly unregistered.
+	func_id: u32,
+	input: Vec<u8>,
+	weight_limit: u64,
+	read_only: bool,
+}
+
+const CALLER: AccountId = AccountId::new([1; 32]);
+const CONTRACT: AccountId = AccountId::new([2; 32]);
+
+/// Every storage item, in key order.
+fn storage() -> BTreeMap<Vec<u8>, Vec<u8>> {
+	let mut items = BTreeMap::new();
+	let mut key = Vec::new();
+	while let Some(next) = sp_io::storage::next_key(&key) {
+		let value = sp_io::storage::get(&next).expect("next_key only returns existing keys");
+		items.insert(next.clone(), value);
+		key = next;
+	}
+	items
+}
+
+/// The storage items added, changed or removed between `before` and `after`, except the events
+/// deposited by `frame_system`, whose writes are paid for by the block.
+fn changed_items(before: &BTreeMap<Vec<u8>, Vec<u8>>, after: &BTreeMap<Vec<u8>, Vec<u8>>) -> u64 {
+	let system = twox_128(b"System");
+	let events: Vec<Vec<u8>> = [&b"Events"[..], b"EventCount", b"EventTopics"]
+		.iter()
+		.map(|item| [system, twox_128(item)].concat())
+		.collect();
+
+	before
+		.keys()
+		.chain(after.keys())
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.filter(|key| !events.iter().any(|prefix| key.starts_with(prefix)))
+		.filter(|key| before.get(*key) != after.get(*key))
+		.count() as u64
+}
+
+fuzz_target!(|call: Call| {
+	let functions = extension_metadata::<Runtime>().functions;
+	let func_id = match call.function {
+		Some(index) => functions[index as usize % functions.len()].id,
+		None => call.func_id,
+	};
+
+	new_test_ext(vec![(CALLER, 1 << 60), (CONTRACT, 1 << 60)]).execute_with(|| {
+		let mut env = MockEnvironment::new(CALLER, CONTRACT).weight_limit(call.weight_limit);
+		env.input = call.input;
+
+		let before = storage();
+		let result =
+			if call.read_only { read_only(|| env.call(func_id)) } else { env.call(func_id) };
+		let changed = changed_items(&before, &storage());
+		let state_changed = changed > 0;
+
+		if let Ok(code) = result {
+			assert!(
+				RetCode::metadata().iter().any(|ret_code| ret_code.code == code),
+				"unknown status code {}",
+				code
+			);
+		}
+		// Errors trap the contract, reverting its changes, so only successful calls must pay
+		if result.is_ok() {
+			let writes = RocksDbWeight::get().write.saturating_mul(changed);
+			assert!(
+				env.charged >= writes,
+				"function {} charged {} for {} changed storage items",
+				func_id,
+				env.charged,
+				changed
+			);
+		}
+		if call.read_only && result.is_ok() {
+			let mutating =
+				functions.iter().any(|function| function.id == func_id && function.mutating);
+			assert!(!(mutating && state_changed), "function {} changed state in a query", func_id);
+		}
+	});
+});
@@ -0,0 +1,138 @@
+//! Chain extension functions backed by `pallet_assets`, allowing contracts to implement PSP22-style
+//! tokens whose balances live in the runtime rather than in contract storage.
+//!
+//! Every function is dispatched with the contract's own account as the signed origin. The
+//! contract becomes the owner, issuer and admin of the assets it creates, and only moves or
+//! approves the assets it holds itself. Assets of other accounts are only moved through the
+//! approvals they gave the contract, as PSP22's `transfer_from` does, so calling a contract never
+//! lets it spend the caller's assets without the caller's consent.
+
+use super::env::ExtensionEnv;
+use codec::Encode;
+use frame_support::traits::tokens::fungibles::approvals::Inspect;
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::{traits::StaticLookup, DispatchError};
+
+type AssetId<T> = <T as pallet_assets::Config>::AssetId;
+type AssetBalance<T> = <T as pallet_assets::Config>::Balance;
+
+pub(super) fn call<T, E>(func_id: u32, env: &mut E) -> Result<(), DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_assets::Config,
+	E: ExtensionEnv<T>,
+{
+	match func_id {
+		// do_asset_create
+		101 => {
+			let (id, min_balance): (AssetId<T>, AssetBalance<T>) = env.read_as()?;
+			let contract = env.address();
+
+			pallet_assets::Pallet::<T>::create(
+				RawOrigin::Signed(contract.clone()).into(),
+				id,
+				T::Lookup::unlookup(contract),
+				min_balance,
+			)?;
+		},
+		// do_asset_mint
+		102 => {
+			let (id, beneficiary, amount): (AssetId<T>, T::AccountId, AssetBalance<T>) =
+				env.read_as()?;
+			let contract = env.address();
+
+			pallet_assets::Pallet::<T>::mint(
+				RawOrigin::Signed(contract).into(),
+				id,
+				T::Lookup::unlookup(beneficiary),
+				amount,
+			)?;
+		},
+		// do_asset_burn
+		103 => {
+			let (id, who, amount): (AssetId<T>, T::AccountId, AssetBalance<T>) = env.read_as()?;
+			let contract = env.address();
+
+			pallet_assets::Pallet::<T>::burn(
+				RawOrigin::Signed(contract).into(),
+				id,
+				T::Lookup::unlookup(who),
+				amount,
+			)?;
+		},
+		// do_asset_transfer
+		104 => {
+			let (id, target, amount): (AssetId<T>, T::AccountId, AssetBalance<T>) =
+				env.read_as()?;
+			let contract = env.address();
+
+			pallet_assets::Pallet::<T>::transfer(
+				RawOrigin::Signed(contract).into(),
+				id,
+				T::Lookup::unlookup(target),
+				amount,
+			)?;
+		},
+		// do_asset_approve
+		105 => {
+			let (id, delegate, amount): (AssetId<T>, T::AccountId, AssetBalance<T>) =
+				env.read_as()?;
+			let contract = env.address();
+
+			pallet_assets::Pallet::<T>::approve_transfer(
+				RawOrigin::Signed(contract).into(),
+				id,
+				T::Lookup::unlookup(delegate),
+				amount,
+			)?;
+		},
+		// do_asset_transfer_approved
+		106 => {
+			let (id, owner, destination, amount): (
+				AssetId<T>,
+				T::AccountId,
+				T::AccountId,
+				AssetBalance<T>,
+			) = env.read_as()?;
+			let contract = env.address();
+
+			pallet_assets::Pallet::<T>::transfer_approved(
+				RawOrigin::Signed(contract).into(),
+				id,
+				T::Lookup::unlookup(owner),
+				T::Lookup::unlookup(destination),
+				amount,
+			)?;
+		},
+		107 | 108 | 109 => {
+			let result = match func_id {
+				// do_asset_balance_of
+				107 => {
+					let (id, who): (AssetId<T>, T::AccountId) = env.read_as()?;
+					pallet_assets::Pallet::<T>::balance(id, who).encode()
+				},
+				// do_asset_total_supply
+				108 => {
+					let id: AssetId<T> = env.read_as()?;
+					pallet_assets::Pallet::<T>::total_supply(id).encode()
+				},
+				// do_asset_allowance
+				109 => {
+					let (id, owner, delegate): (AssetId<T>, T::AccountId, T::AccountId) =
+						env.read_as()?;
+					<pallet_assets::Pallet<T> as Inspect<T::AccountId>>::allowance(
+						id, &owner, &delegate,
+					)
+					.encode()
+				},
+				_ => return Err(DispatchError::Other("Unimplemented func_id")),
+			};
+
+			env.write(&result)
+				.map_err(|_| "Encountered an error when querying asset balances.")?;
+		},
+		_ => return Err(DispatchError::Other("Unimplemented func_id")),
+	}
+
+	Ok(())
+}
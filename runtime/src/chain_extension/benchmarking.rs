@@ -0,0 +1,627 @@
+//! Benchmarks for the chain extension functions.
+//!
+//! Every function is executed from the `call_chain_extension` fixture contract, which calls the
+//! given `func_id` `r` times, so the measured cost includes reading the input from and writing the
+//! output to contract memory. Toggling `r` between zero and one leaves the cost of calling the
+//! fixture itself in the base weight, so the cost of one call is `w(1) - w(0)`. Functions with
+//! variable-sized input or output also have a `_per_byte` benchmark, always calling once with `n`
+//! bytes.
+
+use super::{functions::BALANCE_TRANSFER_V1, storage::MAX_STORAGE_KEY_LEN, RetCode, MAX_INPUT_LEN};
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	storage::unhashed,
+	traits::{Currency, Get},
+};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::UncheckedFrom;
+use pallet_contracts_primitives::Code;
+use pallet_template::BenchmarkHelper;
+use sp_core::{crypto::KeyTypeId, Bytes};
+use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+
+/// Compiled `fixtures/call_chain_extension.wat`.
+const FIXTURE: &[u8] = include_bytes!("../../fixtures/call_chain_extension.wasm");
+
+/// Key type of the keys generated in the benchmark keystore.
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"cext");
+
+type Balance<T> = <T as pallet_balances::Config>::Balance;
+type AssetId<T> = <T as pallet_assets::Config>::AssetId;
+type AssetBalance<T> = <T as pallet_assets::Config>::Balance;
+type ClassId<T> = <T as pallet_uniques::Config>::ClassId;
+type InstanceId<T> = <T as pallet_uniques::Config>::InstanceId;
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+pub trait Config:
+	super::Config
+	+ pallet_contracts::Config<Currency = pallet_balances::Pallet<Self>>
+	+ pallet_template::Config
+	+ pallet_balances::Config
+	+ pallet_assets::Config
+	+ pallet_uniques::Config
+	+ pallet_randomness_collective_flip::Config
+{
+}
+
+/// An instance of the fixture contract and the account calling it.
+struct Fixture<T: Config> {
+	caller: T::AccountId,
+	contract: T::AccountId,
+}
+
+impl<T: Config> Fixture<T>
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+{
+	/// Fund a caller and instantiate the fixture, leaving the contract enough balance to pay the
+	/// deposits of the assets and NFT classes it creates.
+	fn new() -> Result<Self, &'static str> {
+		let caller: T::AccountId = whitelisted_caller();
+		let funding = Balance::<T>::max_value() / 4u32.into();
+		pallet_balances::Pallet::<T>::make_free_balance_be(&caller, funding);
+
+		let contract = pallet_contracts::Pallet::<T>::bare_instantiate(
+			caller.clone(),
+			funding / 2u32.into(),
+			T::BlockWeights::get().max_block,
+			Code::Upload(Bytes(FIXTURE.to_vec())),
+			vec![],
+			vec![],
+			false,
+		)
+		.result
+		.map_err(|_| "Failed to instantiate the fixture.")?
+		.account_id;
+
+		Ok(Self { caller, contract })
+	}
+
+	/// Call `func_id` `repeat` times with `input` from the fixture, returning the status code of
+	/// the last call.
+	fn call(&self, func_id: u32, repeat: u32, input: impl Encode) -> Result<u32, &'static str> {
+		let mut data = (func_id, repeat).encode();
+		input.encode_to(&mut data);
+
+		let output = pallet_contracts::Pallet::<T>::bare_call(
+			self.caller.clone(),
+			self.contract.clone(),
+			0u32.into(),
+			T::BlockWeights::get().max_block,
+			data,
+			false,
+		)
+		.result
+		.map_err(|_| "Failed to call the fixture.")?
+		.data
+		.0;
+
+		let status = output.get(..4).ok_or("Fixture returned no status code.")?;
+		Ok(u32::from_le_bytes(status.try_into().map_err(|_| "Invalid status code.")?))
+	}
+
+	/// Call `func_id` `repeat` times, requiring the calls to succeed.
+	fn call_ok(&self, func_id: u32, repeat: u32, input: impl Encode) -> Result<(), &'static str> {
+		match self.call(func_id, repeat, input)? {
+			status if repeat == 0 || status == RetCode::Success as u32 => Ok(()),
+			_ => Err("Chain extension function did not succeed."),
+		}
+	}
+
+	/// Create an asset owned by the contract, with `amount` minted to the contract.
+	fn create_asset(&self, amount: u32) -> Result<AssetId<T>, &'static str> {
+		let id = AssetId::<T>::default();
+		pallet_assets::Pallet::<T>::create(
+			RawOrigin::Signed(self.contract.clone()).into(),
+			id,
+			T::Lookup::unlookup(self.contract.clone()),
+			1u32.into(),
+		)?;
+		pallet_assets::Pallet::<T>::mint(
+			RawOrigin::Signed(self.contract.clone()).into(),
+			id,
+			T::Lookup::unlookup(self.contract.clone()),
+			amount.into(),
+		)?;
+		Ok(id)
+	}
+
+	/// Create an NFT class administered by the contract, with one instance owned by the contract.
+	fn create_nft(&self) -> Result<(ClassId<T>, InstanceId<T>), &'static str> {
+		let (class, instance) = (ClassId::<T>::default(), InstanceId::<T>::default());
+		pallet_uniques::Pallet::<T>::create(
+			RawOrigin::Signed(self.contract.clone()).into(),
+			class,
+			T::Lookup::unlookup(self.contract.clone()),
+		)?;
+		pallet_uniques::Pallet::<T>::mint(
+			RawOrigin::Signed(self.contract.clone()).into(),
+			class,
+			instance,
+			T::Lookup::unlookup(self.contract.clone()),
+		)?;
+		Ok((class, instance))
+	}
+}
+
+/// Set the longest attribute allowed on `instance` of `class`, or on the class itself, returning
+/// its key.
+fn set_max_attribute<T: Config>(
+	contract: &T::AccountId,
+	class: ClassId<T>,
+	instance: Option<InstanceId<T>>,
+) -> Result<Vec<u8>, &'static str> {
+	let key = vec![1u8; <T as pallet_uniques::Config>::KeyLimit::get() as usize];
+	let value = vec![1u8; <T as pallet_uniques::Config>::ValueLimit::get() as usize];
+	pallet_uniques::Pallet::<T>::set_attribute(
+		RawOrigin::Signed(contract.clone()).into(),
+		class,
+		instance,
+		key.clone().try_into().map_err(|_| "Attribute key is too long.")?,
+		value.try_into().map_err(|_| "Attribute value is too long.")?,
+	)?;
+	Ok(key)
+}
+
+/// A storage key readable through `do_read_storage`, padded to the longest key accepted.
+fn readable_key<T: Config>() -> Result<Vec<u8>, &'static str> {
+	let mut key = <T as super::Config>::ReadableStoragePrefixes::get()
+		.into_iter()
+		.next()
+		.ok_or("No storage is readable by contracts.")?;
+	key.resize(MAX_STORAGE_KEY_LEN, 0);
+	Ok(key)
+}
+
+/// Filter of the events the benchmarks subscribe to.
+fn event_filter<T: Config>() -> T::EventFilter {
+	<T as pallet_template::Config>::BenchmarkHelper::event_filter()
+}
+
+/// Subscribe `count` other contracts to the events subscribed to by the benchmarks, funding their
+/// deposits.
+fn subscribe_others<T: Config>(count: u32) -> Result<(), &'static str> {
+	let deposit = <T as pallet_template::Config>::SubscriptionDeposit::get();
+	for i in 0..count {
+		let subscriber: T::AccountId = account("subscriber", i, 0);
+		<T as pallet_template::Config>::Currency::make_free_balance_be(
+			&subscriber,
+			deposit + deposit,
+		);
+		pallet_template::Pallet::<T>::subscribe(subscriber, event_filter::<T>(), [0; 4])
+			.map_err(|_| "Failed to subscribe.")?;
+	}
+	Ok(())
+}
+
+/// Make requests of `count` accounts other than the fixture, funding their deposits.
+fn request_others<T: Config>(count: u32) -> Result<(), &'static str> {
+	let deposit = <T as pallet_template::Config>::RequestDeposit::get();
+	for i in 0..count {
+		let requester: T::AccountId = account("requester", i, 0);
+		<T as pallet_template::Config>::Currency::make_free_balance_be(
+			&requester,
+			deposit + deposit,
+		);
+		pallet_template::Pallet::<T>::request(
+			requester,
+			[1u8; 4],
+			<T as pallet_template::Config>::MinRequestGasLimit::get(),
+			vec![],
+		)
+		.map_err(|_| "Failed to request.")?;
+	}
+	Ok(())
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+			<T as pallet_contracts::Config>::Call: From<frame_system::Call<T>> + Encode,
+			T::OracleKey: Default,
+			T::OracleValue: From<u32>,
+	}
+
+	store_in_runtime {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+	}: {
+		fixture.call_ok(1, r, 42u32)?;
+	}
+
+	balance_transfer {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		// Worst case, the recipient account is created by the transfer
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let amount: Balance<T> = T::ExistentialDeposit::get();
+	}: {
+		fixture.call_ok(BALANCE_TRANSFER_V1, r, (amount, recipient.clone()))?;
+	}
+
+	get_balance {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+	}: {
+		fixture.call_ok(3, r, fixture.caller.clone())?;
+	}
+
+	get_from_runtime {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+	}: {
+		fixture.call_ok(4, r, ())?;
+	}
+
+	asset_create {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let min_balance: AssetBalance<T> = 1u32.into();
+	}: {
+		fixture.call_ok(101, r, (AssetId::<T>::default(), min_balance))?;
+	}
+
+	asset_mint {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+		// Worst case, the beneficiary has no balance of the asset yet
+		let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+		let amount: AssetBalance<T> = 100u32.into();
+	}: {
+		fixture.call_ok(102, r, (id, beneficiary.clone(), amount))?;
+	}
+
+	asset_burn {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+		// Worst case, the whole balance is burned and the account removed
+		let amount: AssetBalance<T> = 100u32.into();
+	}: {
+		fixture.call_ok(103, r, (id, fixture.caller.clone(), amount))?;
+	}
+
+	asset_transfer {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+		let target: T::AccountId = account("target", 0, 0);
+		let amount: AssetBalance<T> = 100u32.into();
+	}: {
+		fixture.call_ok(104, r, (id, target.clone(), amount))?;
+	}
+
+	asset_approve {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let amount: AssetBalance<T> = 100u32.into();
+	}: {
+		fixture.call_ok(105, r, (id, delegate.clone(), amount))?;
+	}
+
+	asset_transfer_approved {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+		// The contract is the delegate transferring out of the owner's balance
+		let owner: T::AccountId = account("owner", 0, 0);
+		let amount: AssetBalance<T> = 100u32.into();
+		let funding = Balance::<T>::max_value() / 4u32.into();
+		pallet_balances::Pallet::<T>::make_free_balance_be(&owner, funding);
+		pallet_assets::Pallet::<T>::transfer(
+			RawOrigin::Signed(fixture.contract.clone()).into(),
+			id,
+			T::Lookup::unlookup(owner.clone()),
+			amount,
+		)?;
+		pallet_assets::Pallet::<T>::approve_transfer(
+			RawOrigin::Signed(owner.clone()).into(),
+			id,
+			T::Lookup::unlookup(fixture.contract.clone()),
+			amount,
+		)?;
+		let destination: T::AccountId = account("destination", 0, 0);
+	}: {
+		fixture.call_ok(106, r, (id, owner.clone(), destination.clone(), amount))?;
+	}
+
+	asset_balance_of {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+	}: {
+		fixture.call_ok(107, r, (id, fixture.contract.clone()))?;
+	}
+
+	asset_total_supply {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+	}: {
+		fixture.call_ok(108, r, id)?;
+	}
+
+	asset_allowance {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let id = fixture.create_asset(100)?;
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		pallet_assets::Pallet::<T>::approve_transfer(
+			RawOrigin::Signed(fixture.contract.clone()).into(),
+			id,
+			T::Lookup::unlookup(delegate.clone()),
+			100u32.into(),
+		)?;
+	}: {
+		fixture.call_ok(109, r, (id, fixture.contract.clone(), delegate.clone()))?;
+	}
+
+	nft_create_class {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+	}: {
+		fixture.call_ok(201, r, ClassId::<T>::default())?;
+	}
+
+	nft_mint {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let class = ClassId::<T>::default();
+		pallet_uniques::Pallet::<T>::create(
+			RawOrigin::Signed(fixture.contract.clone()).into(),
+			class,
+			T::Lookup::unlookup(fixture.contract.clone()),
+		)?;
+		let owner: Option<T::AccountId> = Some(account("owner", 0, 0));
+	}: {
+		fixture.call_ok(202, r, (class, InstanceId::<T>::default(), owner.clone()))?;
+	}
+
+	nft_burn {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let (class, instance) = fixture.create_nft()?;
+	}: {
+		fixture.call_ok(203, r, (class, instance))?;
+	}
+
+	nft_transfer {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let (class, instance) = fixture.create_nft()?;
+		let dest: T::AccountId = account("dest", 0, 0);
+	}: {
+		fixture.call_ok(204, r, (class, instance, dest.clone()))?;
+	}
+
+	nft_set_attribute {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let (class, instance) = fixture.create_nft()?;
+		let key = vec![1u8; <T as pallet_uniques::Config>::KeyLimit::get() as usize];
+		let value = vec![1u8; <T as pallet_uniques::Config>::ValueLimit::get() as usize];
+	}: {
+		fixture.call_ok(205, r, (class, Some(instance), key.clone(), value.clone()))?;
+	}
+
+	nft_owner_of {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let (class, instance) = fixture.create_nft()?;
+	}: {
+		fixture.call_ok(206, r, (class, instance))?;
+	}
+
+	nft_attribute {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let (class, instance) = fixture.create_nft()?;
+		let key = set_max_attribute::<T>(&fixture.contract, class, Some(instance))?;
+	}: {
+		fixture.call_ok(207, r, (class, Some(instance), key.clone()))?;
+	}
+
+	// `System::remark` is rejected by the chain extension's call filter, so only decoding and
+	// filtering the call is measured. The dispatched call is charged separately by its own
+	// `DispatchInfo`.
+	dispatch_call {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let call = <T as pallet_contracts::Config>::Call::from(
+			frame_system::Call::<T>::remark { remark: vec![] }
+		).encode();
+	}: {
+		fixture.call(301, r, call.clone())?;
+	}
+
+	// Reading and decoding `n` bytes of input, measured with a rejected `do_dispatch_call` as its
+	// input is a single variable-sized call.
+	read_input_per_byte {
+		let n in 0 .. MAX_INPUT_LEN - 16;
+		let fixture = Fixture::<T>::new()?;
+		let call = <T as pallet_contracts::Config>::Call::from(
+			frame_system::Call::<T>::remark { remark: vec![1u8; n as usize] }
+		).encode();
+	}: {
+		fixture.call(301, 1, call.clone())?;
+	}
+
+	random {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+	}: {
+		fixture.call_ok(401, r, Vec::<u8>::new())?;
+	}
+
+	random_per_byte {
+		let n in 0 .. <T as pallet_contracts::Config>::Schedule::get().limits.subject_len;
+		let fixture = Fixture::<T>::new()?;
+		let subject = vec![1u8; n as usize];
+	}: {
+		fixture.call_ok(401, 1, subject.clone())?;
+	}
+
+	sr25519_verify {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &[]).unwrap();
+	}: {
+		fixture.call_ok(501, r, (signature.clone(), Vec::<u8>::new(), public))?;
+	}
+
+	sr25519_verify_per_byte {
+		let n in 0 .. MAX_INPUT_LEN - 128;
+		let fixture = Fixture::<T>::new()?;
+		let message = vec![1u8; n as usize];
+		let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &message).unwrap();
+	}: {
+		fixture.call_ok(501, 1, (signature.clone(), message.clone(), public))?;
+	}
+
+	ed25519_verify {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let public = sp_io::crypto::ed25519_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ed25519_sign(KEY_TYPE, &public, &[]).unwrap();
+	}: {
+		fixture.call_ok(502, r, (signature.clone(), Vec::<u8>::new(), public))?;
+	}
+
+	ed25519_verify_per_byte {
+		let n in 0 .. MAX_INPUT_LEN - 128;
+		let fixture = Fixture::<T>::new()?;
+		let message = vec![1u8; n as usize];
+		let public = sp_io::crypto::ed25519_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ed25519_sign(KEY_TYPE, &public, &message).unwrap();
+	}: {
+		fixture.call_ok(502, 1, (signature.clone(), message.clone(), public))?;
+	}
+
+	ecdsa_verify {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign(KEY_TYPE, &public, &[]).unwrap();
+	}: {
+		fixture.call_ok(503, r, (signature.clone(), Vec::<u8>::new(), public.clone()))?;
+	}
+
+	ecdsa_verify_per_byte {
+		let n in 0 .. MAX_INPUT_LEN - 128;
+		let fixture = Fixture::<T>::new()?;
+		let message = vec![1u8; n as usize];
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign(KEY_TYPE, &public, &message).unwrap();
+	}: {
+		fixture.call_ok(503, 1, (signature.clone(), message.clone(), public.clone()))?;
+	}
+
+	ecdsa_recover {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign(KEY_TYPE, &public, &[]).unwrap();
+	}: {
+		fixture.call_ok(504, r, (signature.clone(), Vec::<u8>::new()))?;
+	}
+
+	ecdsa_recover_per_byte {
+		let n in 0 .. MAX_INPUT_LEN - 128;
+		let fixture = Fixture::<T>::new()?;
+		let message = vec![1u8; n as usize];
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign(KEY_TYPE, &public, &message).unwrap();
+	}: {
+		fixture.call_ok(504, 1, (signature.clone(), message.clone()))?;
+	}
+
+	read_storage {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let key = readable_key::<T>()?;
+		unhashed::put_raw(&key, &[]);
+	}: {
+		fixture.call_ok(601, r, key.clone())?;
+	}
+
+	// `n` is the length of the value read
+	read_storage_per_byte {
+		let n in 0 .. <T as pallet_contracts::Config>::Schedule::get().limits.payload_len;
+		let fixture = Fixture::<T>::new()?;
+		let key = readable_key::<T>()?;
+		unhashed::put_raw(&key, &vec![1u8; n as usize]);
+	}: {
+		fixture.call_ok(601, 1, key.clone())?;
+	}
+
+	emit_signal {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+	}: {
+		fixture.call_ok(701, r, ([1u8; 32], Vec::<u8>::new()))?;
+	}
+
+	emit_signal_per_byte {
+		let n in 0 .. <T as pallet_template::Config>::MaxSignalPayloadLen::get();
+		let fixture = Fixture::<T>::new()?;
+		let payload = vec![1u8; n as usize];
+	}: {
+		fixture.call_ok(701, 1, ([1u8; 32], payload.clone()))?;
+	}
+
+	// Other contracts have all subscriptions but one, the contract subscribes with
+	subscribe {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		subscribe_others::<T>(<T as pallet_template::Config>::MaxSubscriptions::get() - 1)?;
+		let filter = event_filter::<T>().encode();
+	}: {
+		fixture.call_ok(702, r, (filter.clone(), [1u8; 4]))?;
+	}
+
+	unsubscribe {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		subscribe_others::<T>(<T as pallet_template::Config>::MaxSubscriptions::get() - 1)?;
+		pallet_template::Pallet::<T>::subscribe(
+			fixture.contract.clone(),
+			event_filter::<T>(),
+			[1u8; 4],
+		)
+		.map_err(|_| "Failed to subscribe.")?;
+		let filter = event_filter::<T>().encode();
+	}: {
+		fixture.call_ok(703, r, filter.clone())?;
+	}
+
+	// Other contracts have all pending requests but one, the contract requests with the longest
+	// payload
+	request {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		request_others::<T>(<T as pallet_template::Config>::MaxPendingRequests::get() - 1)?;
+		let gas_limit = <T as pallet_template::Config>::MinRequestGasLimit::get();
+		let payload_len = <T as pallet_template::Config>::MaxRequestPayloadLen::get();
+		let payload = vec![1u8; payload_len as usize];
+	}: {
+		fixture.call_ok(704, r, ([1u8; 4], gas_limit, payload.clone()))?;
+	}
+
+	oracle_value {
+		let r in 0 .. 1;
+		let fixture = Fixture::<T>::new()?;
+		let key = T::OracleKey::default();
+		pallet_template::Pallet::<T>::feed(account("feeder", 0, 0), key, 1u32.into())
+			.map_err(|_| "Failed to feed value.")?;
+	}: {
+		fixture.call_ok(705, r, key)?;
+	}
+}
@@ -0,0 +1,235 @@
+//! Generator of the ink! bindings of the chain extension: the `#[ink::chain_extension]` trait, the
+//! types it returns, and the `ContractError` enum with its `FromStatusCode` impl.
+//!
+//! The bindings are generated from `ExtensionMetadata`, so contracts depending on them always use
+//! the ids and types the runtime dispatches on. They are printed by the node's
+//! `chain-extension-bindings` subcommand and checked in as `smart-contracts/extension-bindings`.
+
+use super::metadata::ExtensionMetadata;
+use scale_info::{form::PortableForm, Type, TypeDef, TypeDefPrimitive};
+use std::collections::BTreeMap;
+
+/// Command regenerating the checked-in bindings.
+pub const REGENERATE: &str =
+	"node-template chain-extension-bindings > smart-contracts/extension-bindings/lib.rs";
+
+const HEADER: &str = "\
+//! ink! bindings of the chain extension of the node template runtime.
+//!
+//! Generated from the runtime's chain extension definitions, do not edit. Regenerate with
+";
+
+const IMPORTS: &str = "
+#![cfg_attr(not(feature = \"std\"), no_std)]
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#[allow(unused_imports)]
+use ink_env::{AccountId, Hash};
+use ink_lang as ink;
+#[allow(unused_imports)]
+use ink_prelude::vec::Vec;
+";
+
+const STRUCT_ATTRIBUTES: &str = "\
+/// Output of the chain extension functions returning it, see their documentation.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = \"std\", derive(scale_info::TypeInfo))]
+";
+
+/// Variants of `ContractError` for failures detected by the contract rather than the runtime.
+const CONTRACT_ERRORS: [(&str, &str); 3] = [
+	("FailToCallRuntime", " The chain extension could not be called."),
+	("UnknownStatusCode", " The runtime returned a status code unknown to these bindings."),
+	("InvalidScaleEncoding", " The output of the function could not be decoded."),
+];
+
+/// Source of the `lib.rs` of the ink! bindings of the functions in `metadata`.
+pub fn generate(metadata: &ExtensionMetadata) -> String {
+	let mut types = Types { metadata, structs: BTreeMap::new() };
+
+	let mut functions = String::new();
+	for function in &metadata.functions {
+		// Contracts depending on the bindings call the latest version of each function
+		if function.deprecated {
+			continue
+		}
+		let args = function
+			.args
+			.iter()
+			.map(|arg| format!("{}: {}", arg.name, types.render(arg.ty.id())))
+			.collect::<Vec<_>>()
+			.join(", ");
+		let output = types.render(function.output.id());
+
+		functions.push('\n');
+		functions.push_str(&docs(1, &function.docs));
+		if function.mutating {
+			functions.push_str("\t///\n");
+			functions.push_str(
+				"\t/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.\n",
+			);
+		}
+		functions.push_str(&format!("\t#[ink(extension = {})]\n", function.id));
+		functions.push_str(&format!(
+			"\tfn do_{}({}) -> Result<{}, ContractError>;\n",
+			function.name, args, output
+		));
+	}
+
+	let mut variants = String::new();
+	let mut status_codes = String::new();
+	for (name, doc) in CONTRACT_ERRORS.iter() {
+		variants.push_str(&format!("\t///{}\n\t{},\n", doc, name));
+	}
+	for ret_code in &metadata.ret_codes {
+		if ret_code.code == 0 {
+			status_codes.push_str("\t\t\t0 => Ok(()),\n");
+			continue
+		}
+		variants.push_str(&docs(1, &ret_code.docs));
+		variants.push_str(&format!("\t{},\n", ret_code.name));
+		status_codes
+			.push_str(&format!("\t\t\t{} => Err(Self::{}),\n", ret_code.code, ret_code.name));
+	}
+
+	let mut source = String::from(HEADER);
+	source.push_str(&format!("//! `{}`.\n", REGENERATE));
+	source.push_str(IMPORTS);
+	source.push_str("\n#[ink::chain_extension]\npub trait ChainExtension {\n");
+	source.push_str("\ttype ErrorCode = ContractError;\n");
+	source.push_str(&functions);
+	source.push_str("}\n");
+	for definition in types.structs.values() {
+		source.push('\n');
+		source.push_str(definition);
+	}
+	source.push_str("\n/// Errors of the chain extension functions.\n");
+	source.push_str("#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]\n");
+	source.push_str("#[cfg_attr(feature = \"std\", derive(scale_info::TypeInfo))]\n");
+	source.push_str(&format!("pub enum ContractError {{\n{}}}\n", variants));
+	source.push_str(
+		"
+impl From<scale::Error> for ContractError {
+	fn from(_: scale::Error) -> Self {
+		ContractError::InvalidScaleEncoding
+	}
+}
+
+impl ink_env::chain_extension::FromStatusCode for ContractError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+",
+	);
+	source.push_str(&status_codes);
+	source.push_str("\t\t\t_ => Err(Self::UnknownStatusCode),\n\t\t}\n\t}\n}\n");
+	source
+}
+
+/// Doc comment lines indented by `indent` tabs.
+fn docs(indent: usize, docs: &[String]) -> String {
+	let indent = "\t".repeat(indent);
+	docs.iter().map(|doc| format!("{}///{}\n", indent, doc)).collect()
+}
+
+/// Renders types of the metadata's registry as the types ink! contracts use.
+struct Types<'a> {
+	metadata: &'a ExtensionMetadata,
+	/// Definitions of the structs returned by the functions, by name.
+	structs: BTreeMap<String, String>,
+}
+
+impl<'a> Types<'a> {
+	fn resolve(&self, id: u32) -> &'a Type<PortableForm> {
+		self.metadata
+			.types
+			.resolve(id)
+			.expect("types of the metadata are in its registry; qed")
+	}
+
+	/// The ink! type of the type with `id`, defining it if it is a struct.
+	fn render(&mut self, id: u32) -> String {
+		let ty = self.resolve(id);
+		match ty.type_def() {
+			TypeDef::Primitive(primitive) => primitive_name(primitive).into(),
+			TypeDef::Sequence(sequence) =>
+				format!("Vec<{}>", self.render(sequence.type_param().id())),
+			TypeDef::Array(array) =>
+				format!("[{}; {}]", self.render(array.type_param().id()), array.len()),
+			TypeDef::Tuple(tuple) => match tuple.fields() {
+				[] => "()".into(),
+				[field] => format!("({},)", self.render(field.id())),
+				fields => {
+					let fields = fields.iter().map(|field| self.render(field.id()));
+					format!("({})", fields.collect::<Vec<_>>().join(", "))
+				},
+			},
+			TypeDef::Variant(variant) if ty.path().ident().as_deref() == Some("Option") => {
+				let some = variant
+					.variants()
+					.iter()
+					.find(|variant| variant.name() == "Some")
+					.expect("`Option` has a `Some` variant; qed");
+				format!("Option<{}>", self.render(some.fields()[0].ty().id()))
+			},
+			TypeDef::Composite(composite) => match ty.path().ident().as_deref() {
+				// The runtime's `AccountId` and `Hash` are those of the ink! default environment
+				Some("AccountId32") => "AccountId".into(),
+				Some("H256") => "Hash".into(),
+				// Newtypes, such as public keys and signatures, are encoded as their field
+				_ if composite.fields().len() == 1 && composite.fields()[0].name().is_none() =>
+					self.render(composite.fields()[0].ty().id()),
+				Some(name) => {
+					let name = name.to_string();
+					if !self.structs.contains_key(&name) {
+						let mut definition = String::from(STRUCT_ATTRIBUTES);
+						definition.push_str(&format!("pub struct {} {{\n", name));
+						for field in composite.fields() {
+							let field_name = field.name().expect("struct fields are named; qed");
+							let field_ty = self.render(field.ty().id());
+							definition.push_str(&format!("\tpub {}: {},\n", field_name, field_ty));
+						}
+						definition.push_str("}\n");
+						self.structs.insert(name.clone(), definition);
+					}
+					name
+				},
+				None => panic!("Type {:?} has no ink! binding", ty),
+			},
+			_ => panic!("Type {:?} has no ink! binding", ty),
+		}
+	}
+}
+
+fn primitive_name(primitive: &TypeDefPrimitive) -> &'static str {
+	match primitive {
+		TypeDefPrimitive::Bool => "bool",
+		TypeDefPrimitive::Char => "char",
+		TypeDefPrimitive::Str => "ink_prelude::string::String",
+		TypeDefPrimitive::U8 => "u8",
+		TypeDefPrimitive::U16 => "u16",
+		TypeDefPrimitive::U32 => "u32",
+		TypeDefPrimitive::U64 => "u64",
+		TypeDefPrimitive::U128 => "u128",
+		TypeDefPrimitive::I8 => "i8",
+		TypeDefPrimitive::I16 => "i16",
+		TypeDefPrimitive::I32 => "i32",
+		TypeDefPrimitive::I64 => "i64",
+		TypeDefPrimitive::I128 => "i128",
+		primitive => panic!("Primitive {:?} has no ink! binding", primitive),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{chain_extension::extension_metadata, Runtime};
+
+	#[test]
+	fn checked_in_bindings_are_up_to_date() {
+		let bindings = super::generate(&extension_metadata::<Runtime>());
+		assert!(
+			bindings == include_str!("../../../smart-contracts/extension-bindings/lib.rs"),
+			"The ink! bindings are out of date, regenerate them with `{}`",
+			super::REGENERATE,
+		);
+	}
+}
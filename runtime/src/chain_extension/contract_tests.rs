@@ -0,0 +1,279 @@
+//! End-to-end tests of the chain extension: the `call_chain_extension` fixture is deployed to the
+//! runtime, then called through `pallet_contracts` and `TemplateModule::call_smart_contract`, so
+//! every function is served by `ExampleExtension` as it is to deployed contracts.
+
+use super::{functions, testing::new_test_ext, RetCode};
+use crate::{
+	AccountId, Assets, Balance, Balances, BlockNumber, Call, Contracts, Democracy, Event,
+	EventFilter, Hash, MinRequestGasLimit, Origin, Runtime, System, TemplateModule, UNIT,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_ok,
+	storage::storage_prefix,
+	traits::{Get, PalletInfoAccess},
+	weights::Weight,
+};
+use pallet_contracts_primitives::Code;
+use pallet_template::{ContractOrigin, Permission, TimestampedValue};
+use sp_core::{ecdsa, ed25519, sr25519, Bytes, Pair};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash as _},
+	DispatchError,
+};
+use sp_std::collections::btree_set::BTreeSet;
+
+/// Calls the chain extension function given by its input, see the fixture's source.
+const FIXTURE: &[u8] = include_bytes!("../../fixtures/call_chain_extension.wasm");
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+
+/// The fixture deployed by `ALICE`, recording the functions it was made to call.
+struct Fixture {
+	contract: AccountId,
+	called: BTreeSet<u32>,
+}
+
+impl Fixture {
+	fn gas_limit() -> Weight {
+		<Runtime as frame_system::Config>::BlockWeights::get().max_block
+	}
+
+	/// Deploy the fixture, endowed with enough balance to pay the deposits of what it creates.
+	fn deploy() -> Self {
+		let contract = Contracts::bare_instantiate(
+			ALICE,
+			10_000 * UNIT,
+			Self::gas_limit(),
+			Code::Upload(Bytes(FIXTURE.to_vec())),
+			vec![],
+			vec![],
+			false,
+		)
+		.result
+		.expect("the fixture deploys")
+		.account_id;
+		Self { contract, called: BTreeSet::new() }
+	}
+
+	/// Make the fixture call `func_id` once with `input`, returning the status code and output of
+	/// the function, or the error trapping the contract.
+	fn call(&mut self, func_id: u32, input: impl Encode) -> Result<(u32, Vec<u8>), DispatchError> {
+		self.called.insert(func_id);
+		let mut data = (func_id, 1u32).encode();
+		input.encode_to(&mut data);
+
+		let output =
+			Contracts::bare_call(ALICE, self.contract.clone(), 0, Self::gas_limit(), data, false)
+				.result?
+				.data
+				.0;
+		let (status, output) = output.split_at(4);
+		Ok((u32::decode(&mut &status[..]).unwrap(), output.to_vec()))
+	}
+
+	/// Make the fixture call `func_id` once with `input`, requiring it to succeed, and return the
+	/// output decoded as `O`.
+	fn call_ok<O: Decode>(&mut self, func_id: u32, input: impl Encode) -> O {
+		let (status, output) = self.call(func_id, input).expect("the function returns");
+		assert_eq!(status, RetCode::Success as u32, "func_id {} failed", func_id);
+		O::decode(&mut &output[..]).expect("the output has the type of the function")
+	}
+}
+
+/// `new_test_ext` with `ALICE` funded to deploy the fixture and `BOB` existing.
+fn build_ext() -> sp_io::TestExternalities {
+	new_test_ext(vec![(ALICE, 1_000_000 * UNIT), (BOB, UNIT)])
+}
+
+fn call_basic_functions(fixture: &mut Fixture) {
+	fixture.call_ok::<()>(1, 42u32);
+	assert_eq!(TemplateModule::get_value(), 42);
+	assert_eq!(fixture.call_ok::<u32>(4, ()), 42);
+
+	// The deprecated version 0 and version 1 of the transfer both move the caller's funds
+	fixture.call_ok::<()>(2, (1_000u32, BOB));
+	fixture.call_ok::<()>(functions::BALANCE_TRANSFER_V1, (UNIT, BOB));
+	assert_eq!(Balances::free_balance(&BOB), 2 * UNIT + 1_000);
+	assert_eq!(fixture.call_ok::<Balance>(3, BOB), 2 * UNIT + 1_000);
+}
+
+fn call_asset_functions(fixture: &mut Fixture) {
+	let contract = fixture.contract.clone();
+	fixture.call_ok::<()>(101, (1u32, 1u128));
+	fixture.call_ok::<()>(102, (1u32, contract.clone(), 100u128));
+	fixture.call_ok::<()>(102, (1u32, ALICE, 100u128));
+	fixture.call_ok::<()>(104, (1u32, BOB, 10u128));
+	fixture.call_ok::<()>(105, (1u32, BOB, 20u128));
+	assert_eq!(fixture.call_ok::<u128>(109, (1u32, contract.clone(), BOB)), 20);
+
+	// The contract spends the assets of its caller only through the approvals it was given
+	assert!(fixture.call(106, (1u32, ALICE, BOB, 10u128)).is_err());
+	assert_ok!(Assets::approve_transfer(Origin::signed(ALICE), 1, contract.clone().into(), 30));
+	fixture.call_ok::<()>(106, (1u32, ALICE, BOB, 10u128));
+	assert_eq!(fixture.call_ok::<u128>(107, (1u32, BOB)), 20);
+
+	fixture.call_ok::<()>(103, (1u32, BOB, 5u128));
+	assert_eq!(fixture.call_ok::<u128>(108, 1u32), 195);
+}
+
+fn call_nft_functions(fixture: &mut Fixture) {
+	let contract = fixture.contract.clone();
+	fixture.call_ok::<()>(201, 1u32);
+	fixture.call_ok::<()>(202, (1u32, 1u32, None::<AccountId>));
+	fixture.call_ok::<()>(202, (1u32, 2u32, Some(BOB)));
+	fixture.call_ok::<()>(205, (1u32, Some(1u32), b"key".to_vec(), b"value".to_vec()));
+	assert_eq!(
+		fixture.call_ok::<Option<Vec<u8>>>(207, (1u32, Some(1u32), b"key".to_vec())),
+		Some(b"value".to_vec()),
+	);
+	assert_eq!(fixture.call_ok::<Option<AccountId>>(206, (1u32, 1u32)), Some(contract));
+	fixture.call_ok::<()>(204, (1u32, 1u32, BOB));
+	assert_eq!(fixture.call_ok::<Option<AccountId>>(206, (1u32, 1u32)), Some(BOB));
+	fixture.call_ok::<()>(203, (1u32, 2u32));
+	assert_eq!(fixture.call_ok::<Option<AccountId>>(206, (1u32, 2u32)), None);
+}
+
+fn call_dispatch_functions(fixture: &mut Fixture) {
+	let transfer =
+		Call::Balances(pallet_balances::Call::transfer { dest: BOB.into(), value: UNIT });
+	let bob_balance = Balances::free_balance(&BOB);
+	fixture.call_ok::<()>(301, transfer.encode());
+	assert_eq!(Balances::free_balance(&BOB), bob_balance + UNIT);
+
+	// Dispatched with the contract's `ContractOrigin`, which can't sign a transfer
+	let (status, _) = fixture.call(302, transfer.encode()).unwrap();
+	assert_eq!(status, RetCode::DispatchFailed as u32);
+	assert_eq!(Balances::free_balance(&BOB), bob_balance + UNIT);
+
+	let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+	let (status, _) = fixture.call(301, remark.encode()).unwrap();
+	assert_eq!(status, RetCode::CallFiltered as u32);
+}
+
+fn call_randomness_and_crypto_functions(fixture: &mut Fixture) {
+	let (_, determined_at): (Hash, BlockNumber) = fixture.call_ok(401, b"subject".to_vec());
+	assert_eq!(determined_at, 0);
+
+	let message = b"voucher".to_vec();
+	let sr25519 = sr25519::Pair::from_seed(&[1; 32]);
+	let input = (sr25519.sign(&message), message.clone(), sr25519.public());
+	assert!(fixture.call_ok::<bool>(501, input));
+	let ed25519 = ed25519::Pair::from_seed(&[1; 32]);
+	let input = (ed25519.sign(&message), message.clone(), ed25519.public());
+	assert!(fixture.call_ok::<bool>(502, input));
+	let ecdsa = ecdsa::Pair::from_seed(&[1; 32]);
+	let signature = ecdsa.sign(&message);
+	assert!(fixture.call_ok::<bool>(503, (signature.clone(), message.clone(), ecdsa.public())));
+	assert_eq!(
+		fixture.call_ok::<Option<[u8; 33]>>(504, (signature, message)),
+		Some(ecdsa.public().0)
+	);
+}
+
+fn call_storage_and_event_functions(fixture: &mut Fixture) {
+	let key = storage_prefix(TemplateModule::name().as_bytes(), b"ContractEntry").to_vec();
+	assert_eq!(fixture.call_ok::<Option<Vec<u8>>>(601, key), Some(42u32.encode()));
+
+	fixture.call_ok::<()>(701, ([1u8; 32], vec![1u8, 2, 3]));
+	assert!(System::events().iter().any(|record| matches!(
+		&record.event,
+		Event::TemplateModule(pallet_template::Event::ContractSignal { contract, .. })
+			if *contract == fixture.contract
+	)));
+
+	let filter = EventFilter::ContractEntryChanged.encode();
+	fixture.call_ok::<()>(702, (filter.clone(), [0u8; 4]));
+	fixture.call_ok::<()>(703, filter);
+
+	assert_eq!(fixture.call_ok::<u64>(704, ([0u8; 4], MinRequestGasLimit::get(), vec![1u8])), 0);
+	assert_eq!(fixture.call_ok::<Option<TimestampedValue<u128>>>(705, 1u32), None);
+}
+
+fn call_governance_functions(fixture: &mut Fixture) {
+	let proposal = Call::System(frame_system::Call::remark { remark: vec![] }).encode();
+	let proposal_hash = BlakeTwo256::hash(&proposal);
+	let (status, _) = fixture.call(801, proposal.clone()).unwrap();
+	assert_eq!(status, RetCode::NotPermitted as u32);
+
+	assert_ok!(TemplateModule::grant_permission(
+		Origin::root(),
+		fixture.contract.clone(),
+		Permission::GovernanceContract
+	));
+	fixture.call_ok::<()>(801, proposal);
+	fixture.call_ok::<()>(802, (proposal_hash, 100 * UNIT));
+	fixture.call_ok::<()>(803, (0u32, 1u32));
+	assert_eq!(Democracy::public_props().len(), 1);
+
+	let threshold = pallet_democracy::VoteThreshold::SuperMajorityApprove;
+	let referendum = Democracy::internal_start_referendum(proposal_hash, threshold, 0);
+	fixture.call_ok::<()>(804, (referendum, true, 1u8, 10 * UNIT));
+	assert!(Democracy::referendum_status(referendum).unwrap().tally.ayes > 0);
+}
+
+#[test]
+fn every_function_is_served_to_a_deployed_contract() {
+	build_ext().execute_with(|| {
+		let mut fixture = Fixture::deploy();
+
+		call_basic_functions(&mut fixture);
+		call_asset_functions(&mut fixture);
+		call_nft_functions(&mut fixture);
+		call_dispatch_functions(&mut fixture);
+		call_randomness_and_crypto_functions(&mut fixture);
+		call_storage_and_event_functions(&mut fixture);
+		call_governance_functions(&mut fixture);
+
+		let registered: BTreeSet<u32> =
+			functions::metadata::<Runtime>().into_iter().map(|f| f.id).collect();
+		assert_eq!(fixture.called, registered, "every registered function is called");
+	})
+}
+
+#[test]
+fn contract_origin_has_code_hash_of_the_deployed_fixture() {
+	build_ext().execute_with(|| {
+		let fixture = Fixture::deploy();
+		assert_eq!(
+			TemplateModule::contract_origin(fixture.contract.clone()),
+			Some(ContractOrigin {
+				address: fixture.contract,
+				code_hash: BlakeTwo256::hash(FIXTURE)
+			})
+		);
+		assert_eq!(TemplateModule::contract_origin(ALICE), None);
+	})
+}
+
+#[test]
+fn unknown_func_id_traps_the_contract() {
+	build_ext().execute_with(|| {
+		let mut fixture = Fixture::deploy();
+		assert!(fixture.call(999, ()).is_err());
+	})
+}
+
+#[test]
+fn pallet_calls_contract_calling_back_into_the_runtime() {
+	build_ext().execute_with(|| {
+		let fixture = Fixture::deploy();
+		// The fixture takes the func_id and the number of calls as the selector, then the argument
+		// of `call_smart_contract` as the input of the function
+		assert_ok!(TemplateModule::call_smart_contract(
+			Origin::signed(ALICE),
+			fixture.contract.clone(),
+			(1u32, 1u32).encode(),
+			9,
+			Fixture::gas_limit() / 2,
+		));
+		assert_eq!(TemplateModule::get_value(), 9);
+		System::assert_has_event(Event::TemplateModule(
+			pallet_template::Event::CalledPalletFromContract(9),
+		));
+		System::assert_last_event(Event::TemplateModule(
+			pallet_template::Event::CalledContractFromPallet(fixture.contract),
+		));
+	})
+}
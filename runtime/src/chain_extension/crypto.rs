@@ -0,0 +1,54 @@
+//! Chain extension functions verifying signatures over contract-provided messages, so contracts
+//! can validate off-chain signed payloads such as vouchers and permits.
+//!
+//! Weights are charged by the length of the input, before it is read, so contracts pay for hashing
+//! the full message.
+
+use super::env::ExtensionEnv;
+use codec::Encode;
+use pallet_contracts::chain_extension::SysConfig;
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub(super) fn call<T, E>(func_id: u32, env: &mut E) -> Result<(), DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config,
+	E: ExtensionEnv<T>,
+{
+	let result = match func_id {
+		// do_sr25519_verify
+		501 => {
+			let (signature, message, public): (sr25519::Signature, Vec<u8>, sr25519::Public) =
+				env.read_as()?;
+			sp_io::crypto::sr25519_verify(&signature, &message, &public).encode()
+		},
+		// do_ed25519_verify
+		502 => {
+			let (signature, message, public): (ed25519::Signature, Vec<u8>, ed25519::Public) =
+				env.read_as()?;
+			sp_io::crypto::ed25519_verify(&signature, &message, &public).encode()
+		},
+		// do_ecdsa_verify
+		503 => {
+			let (signature, message, public): (ecdsa::Signature, Vec<u8>, ecdsa::Public) =
+				env.read_as()?;
+			sp_io::crypto::ecdsa_verify(&signature, &message, &public).encode()
+		},
+		// do_ecdsa_recover
+		504 => {
+			let (signature, message): (ecdsa::Signature, Vec<u8>) = env.read_as()?;
+			// The message is hashed the same way `ecdsa_verify` and `ecdsa_sign` do
+			let message_hash = sp_io::hashing::blake2_256(&message);
+			sp_io::crypto::secp256k1_ecdsa_recover_compressed(&signature.0, &message_hash)
+				.ok()
+				.encode()
+		},
+		_ => return Err(DispatchError::Other("Unimplemented func_id")),
+	};
+
+	env.write(&result)
+		.map_err(|_| "Encountered an error when writing the verification result.")?;
+
+	Ok(())
+}
@@ -0,0 +1,94 @@
+//! Chain extension functions dispatching an arbitrary, whitelisted runtime call on behalf of a
+//! contract.
+//!
+//! The contract passes the SCALE-encoded runtime `Call`. It is only dispatched if it passes the
+//! chain extension's own `Config::CallFilter`, and always with the contract's own account as the
+//! signed origin, or with its `pallet_template::ContractOrigin` for calls gated on specific
+//! contracts. The call is dispatched in its own storage transaction, so a failing call leaves no
+//! changes behind. Failures the contract is expected to handle are reported as a `RetCode` instead
+//! of trapping.
+
+use super::{env::ExtensionEnv, Config, RetCode};
+use codec::DecodeLimit;
+use frame_support::{
+	log::error,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Contains, Get},
+	weights::GetDispatchInfo,
+};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::{SysConfig, UncheckedFrom};
+use sp_runtime::{
+	traits::{Dispatchable, IsType},
+	DispatchError,
+};
+use sp_std::vec::Vec;
+
+/// Maximum nesting depth accepted when decoding a call, matching the limit applied to extrinsics.
+const MAX_CALL_DEPTH: u32 = 256;
+
+pub(super) fn call<T, E>(func_id: u32, env: &mut E) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_template::Config + Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	<T as SysConfig>::Origin: From<pallet_template::Origin<T>>,
+	E: ExtensionEnv<T>,
+{
+	let encoded_call: Vec<u8> = env.read_as()?;
+	let call = match <T as pallet_contracts::Config>::Call::decode_with_depth_limit(
+		MAX_CALL_DEPTH,
+		&mut &encoded_call[..],
+	) {
+		Ok(call) => call,
+		Err(_) => return Ok(RetCode::InvalidCall),
+	};
+
+	if !<T as Config>::CallFilter::contains(call.into_ref()) {
+		return Ok(RetCode::CallFiltered)
+	}
+
+	// Decoding and filtering the call has been charged already, the call itself is charged once
+	// dispatched
+	let contract = env.address();
+	let origin: <T as SysConfig>::Origin = match func_id {
+		// do_dispatch_call
+		301 => RawOrigin::Signed(contract).into(),
+		// do_dispatch_call_as_contract
+		302 => {
+			// Reading the code hash of the contract is not part of the benchmarked weight
+			env.charge_weight(T::DbWeight::get().reads(1))?;
+			pallet_template::Pallet::<T>::contract_origin(contract)
+				.ok_or("The contract has no origin until it is instantiated.")?
+				.into()
+		},
+		_ => return Err(DispatchError::Other("Unimplemented func_id")),
+	};
+
+	dispatch_as::<T, E>(env, call, origin)
+}
+
+/// Dispatch `call` with `origin` in its own storage transaction, charging its weight and reporting
+/// its failure as `RetCode::DispatchFailed`, with its changes rolled back. Also used by the
+/// functions dispatching calls of a given pallet.
+pub(super) fn dispatch_as<T, E>(
+	env: &mut E,
+	call: <T as pallet_contracts::Config>::Call,
+	origin: <T as SysConfig>::Origin,
+) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config,
+	E: ExtensionEnv<T>,
+{
+	let dispatch_info = call.get_dispatch_info();
+	// Refund the difference if the call used less than its pre-dispatch weight
+	env.charge_weight_then_adjust(dispatch_info.weight, || {
+		let (post_info, ret_code) = with_transaction(|| match call.dispatch(origin) {
+			Ok(post_info) => TransactionOutcome::Commit((post_info, RetCode::Success)),
+			Err(e) => {
+				error!("Runtime call dispatched from a contract failed: {:?}", e.error);
+				TransactionOutcome::Rollback((e.post_info, RetCode::DispatchFailed))
+			},
+		});
+		(ret_code, post_info.calc_actual_weight(&dispatch_info))
+	})
+}
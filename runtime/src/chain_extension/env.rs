@@ -0,0 +1,88 @@
+//! The parts of the `pallet_contracts` chain extension `Environment` used by the functions.
+//!
+//! The functions are written against `ExtensionEnv` rather than `Environment`, which can only be
+//! created by `pallet_contracts` while executing a contract, so they can also be called with the
+//! `testing::MockEnvironment` of unit tests.
+
+use codec::Decode;
+use frame_support::weights::Weight;
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, Environment, Ext, SysConfig, UncheckedFrom,
+};
+use sp_runtime::DispatchError;
+
+/// Access to the input, output, weight meter and accounts of a chain extension call.
+pub trait ExtensionEnv<T: SysConfig> {
+	/// Length of the input passed by the contract.
+	fn in_len(&self) -> u32;
+
+	/// Decode the whole input passed by the contract.
+	fn read_as<V: Decode>(&mut self) -> Result<V, DispatchError>;
+
+	/// Write `output` to the output buffer of the contract.
+	fn write(&mut self, output: &[u8]) -> Result<(), DispatchError>;
+
+	/// Charge `weight`, failing if it exceeds the weight left to the call.
+	fn charge_weight(&mut self, weight: Weight) -> Result<(), DispatchError>;
+
+	/// Charge `weight`, then execute `f` and adjust the charge to the actual weight it returns.
+	fn charge_weight_then_adjust<R>(
+		&mut self,
+		weight: Weight,
+		f: impl FnOnce() -> (R, Weight),
+	) -> Result<R, DispatchError>;
+
+	/// Account calling the contract.
+	fn caller(&mut self) -> T::AccountId;
+
+	/// Account of the contract.
+	fn address(&mut self) -> T::AccountId;
+
+	/// Append `msg` to the debug output of the call, returning whether debug output is collected.
+	fn append_debug_buffer(&mut self, msg: &str) -> bool;
+}
+
+impl<'a, 'b, E> ExtensionEnv<E::T> for Environment<'a, 'b, E, BufInBufOutState>
+where
+	E: Ext,
+	<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	fn in_len(&self) -> u32 {
+		Environment::in_len(self)
+	}
+
+	fn read_as<V: Decode>(&mut self) -> Result<V, DispatchError> {
+		Environment::read_as(self)
+	}
+
+	fn write(&mut self, output: &[u8]) -> Result<(), DispatchError> {
+		Environment::write(self, output, false, None)
+	}
+
+	fn charge_weight(&mut self, weight: Weight) -> Result<(), DispatchError> {
+		Environment::charge_weight(self, weight).map(|_| ())
+	}
+
+	fn charge_weight_then_adjust<R>(
+		&mut self,
+		weight: Weight,
+		f: impl FnOnce() -> (R, Weight),
+	) -> Result<R, DispatchError> {
+		let charged = Environment::charge_weight(self, weight)?;
+		let (result, actual_weight) = f();
+		self.adjust_weight(charged, actual_weight);
+		Ok(result)
+	}
+
+	fn caller(&mut self) -> <E::T as SysConfig>::AccountId {
+		self.ext().caller().clone()
+	}
+
+	fn address(&mut self) -> <E::T as SysConfig>::AccountId {
+		self.ext().address().clone()
+	}
+
+	fn append_debug_buffer(&mut self, msg: &str) -> bool {
+		self.ext().append_debug_buffer(msg)
+	}
+}
@@ -0,0 +1,329 @@
+//! Definitions of the chain extension functions: their ids, the types they take and return,
+//! whether they change state, how they are weighed and whether they are deprecated.
+//!
+//! Changing the types of a function breaks the contracts calling it, so a new version of the
+//! function is added instead, see `versioned`. The previous version is marked `deprecated` and
+//! kept, adapted to the new version in `ExampleExtension`, until it is retired.
+//!
+//! `ExampleExtension` looks a function up here to charge and check it before executing it. The
+//! metadata returned by `ChainExtensionApi`, and the ink! bindings generated from it, come from the
+//! same definitions, so a new function is only available to contracts once it is listed here.
+
+use super::{
+	metadata::{ArgMetadata, FunctionMetadata, WeightFormula},
+	randomness::RandomOutput,
+	versioned, ChainExtensionWeightInfo, Config,
+};
+use frame_support::{traits::Currency, weights::Weight};
+use pallet_template::TimestampedValue;
+use scale_info::meta_type;
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_std::{vec, vec::Vec};
+
+type W<T> = <T as Config>::WeightInfo;
+type AccountId<T> = <T as frame_system::Config>::AccountId;
+type Balance<T> = <T as pallet_balances::Config>::Balance;
+type AssetId<T> = <T as pallet_assets::Config>::AssetId;
+type AssetBalance<T> = <T as pallet_assets::Config>::Balance;
+type ClassId<T> = <T as pallet_uniques::Config>::ClassId;
+type InstanceId<T> = <T as pallet_uniques::Config>::InstanceId;
+type DemocracyBalance<T> =
+	<<T as pallet_democracy::Config>::Currency as Currency<AccountId<T>>>::Balance;
+
+/// What `ExampleExtension` needs to know about a function before executing it.
+pub(super) struct Function {
+	/// Whether the function changes state.
+	pub mutating: bool,
+	/// How the function is weighed.
+	pub weight: WeightFormula,
+	/// Warning appended to the debug output of calls of a deprecated function.
+	pub deprecation: Option<&'static str>,
+}
+
+/// `do_balance_transfer` taking a `Balance` amount, replacing version 0 taking a `u32`.
+pub(super) const BALANCE_TRANSFER_V1: u32 = versioned(1, 2);
+
+/// Slope of a benchmarked weight function, which is the weight of one call for the benchmarks
+/// calling a function `r` times from the fixture contract, and the weight of one byte for the
+/// `_per_byte` ones.
+fn slope(weight: fn(u32) -> Weight) -> Weight {
+	weight(1).saturating_sub(weight(0))
+}
+
+/// Weight of a function benchmarked by `call` that reads its input at the generic cost.
+fn weighed<T: Config>(call: fn(u32) -> Weight) -> WeightFormula {
+	WeightFormula {
+		base: slope(call),
+		per_input_byte: slope(W::<T>::read_input_per_byte),
+		..Default::default()
+	}
+}
+
+/// Weight of a function benchmarked by `call` that does work for each byte of input it reads,
+/// benchmarked by `per_input_byte`.
+fn weighed_per_input_byte<T: Config>(
+	call: fn(u32) -> Weight,
+	per_input_byte: fn(u32) -> Weight,
+) -> WeightFormula {
+	let weight = weighed::<T>(call);
+	WeightFormula { per_input_byte: weight.per_input_byte.max(slope(per_input_byte)), ..weight }
+}
+
+macro_rules! is_mutating {
+	(mutating) => {
+		true
+	};
+	(view) => {
+		false
+	};
+}
+
+macro_rules! deprecation {
+	($name:ident, $id:expr) => {
+		None
+	};
+	($name:ident, $id:expr, deprecated) => {
+		Some(concat!(
+			"Called chain extension function `",
+			stringify!($name),
+			"` with the deprecated func_id ",
+			stringify!($id),
+			", it will be refused once retired. Call its latest version instead.\n",
+		))
+	};
+}
+
+macro_rules! functions {
+	($(
+		$( #[doc = $doc:literal] )*
+		$id:expr => fn $name:ident($( $arg:ident: $arg_ty:ty ),*) -> $output:ty,
+			$mutability:ident, $weight:expr $(, $deprecated:ident )?;
+	)*) => {
+		/// The function called with `func_id`, if any.
+		pub(super) fn lookup<T>(func_id: u32) -> Option<Function>
+		where
+			T: Config
+				+ pallet_template::Config
+				+ pallet_balances::Config
+				+ pallet_assets::Config
+				+ pallet_uniques::Config
+				+ pallet_democracy::Config,
+		{
+			$(
+				if func_id == $id {
+					return Some(Function {
+						mutating: is_mutating!($mutability),
+						weight: $weight,
+						deprecation: deprecation!($name, $id $(, $deprecated )?),
+					})
+				}
+			)*
+			None
+		}
+
+		/// Metadata of every function, ordered by id, then version.
+		pub(super) fn metadata<T>() -> Vec<FunctionMetadata>
+		where
+			T: Config
+				+ pallet_template::Config
+				+ pallet_balances::Config
+				+ pallet_assets::Config
+				+ pallet_uniques::Config
+				+ pallet_democracy::Config,
+		{
+			vec![$(
+				FunctionMetadata {
+					id: $id,
+					name: stringify!($name),
+					docs: vec![$( $doc ),*],
+					args: vec![$(
+						ArgMetadata { name: stringify!($arg), ty: meta_type::<$arg_ty>() },
+					)*],
+					output: meta_type::<$output>(),
+					mutating: is_mutating!($mutability),
+					weight: $weight,
+					deprecated: deprecation!($name, $id $(, $deprecated )?).is_some(),
+				},
+			)*]
+		}
+	};
+}
+
+functions! {
+	/// Stores `value` in `pallet_template`, as the caller of the contract.
+	1 => fn store_in_runtime(value: u32) -> (), mutating, weighed::<T>(W::<T>::store_in_runtime);
+	/// Transfers `value` of the native currency from the caller of the contract to `recipient`.
+	2 => fn balance_transfer(value: u32, recipient: AccountId<T>) -> (), mutating,
+		weighed::<T>(W::<T>::balance_transfer), deprecated;
+	/// Transfers `value` of the native currency from the caller of the contract to `recipient`.
+	BALANCE_TRANSFER_V1 => fn balance_transfer(value: Balance<T>, recipient: AccountId<T>) -> (),
+		mutating, weighed::<T>(W::<T>::balance_transfer);
+	/// Returns the free balance of `account`.
+	3 => fn get_balance(account: AccountId<T>) -> Balance<T>, view,
+		weighed::<T>(W::<T>::get_balance);
+	/// Returns the value stored in `pallet_template`.
+	4 => fn get_from_runtime() -> u32, view, weighed::<T>(W::<T>::get_from_runtime);
+
+	/// Creates a new runtime asset with the given id. The contract becomes its owner and admin.
+	101 => fn asset_create(asset_id: AssetId<T>, min_balance: AssetBalance<T>) -> (), mutating,
+		weighed::<T>(W::<T>::asset_create);
+	/// Mints `amount` of the asset to `beneficiary`. Only succeeds for assets the contract issues.
+	102 => fn asset_mint(asset_id: AssetId<T>, beneficiary: AccountId<T>, amount: AssetBalance<T>)
+		-> (), mutating, weighed::<T>(W::<T>::asset_mint);
+	/// Burns up to `amount` of the asset held by `who`. Only succeeds for assets the contract
+	/// administers.
+	103 => fn asset_burn(asset_id: AssetId<T>, who: AccountId<T>, amount: AssetBalance<T>) -> (),
+		mutating, weighed::<T>(W::<T>::asset_burn);
+	/// Transfers `amount` of the asset from the contract's own balance to `target`.
+	104 => fn asset_transfer(asset_id: AssetId<T>, target: AccountId<T>, amount: AssetBalance<T>)
+		-> (), mutating, weighed::<T>(W::<T>::asset_transfer);
+	/// Approves `delegate` to transfer up to `amount` of the asset out of the contract's own
+	/// balance.
+	105 => fn asset_approve(asset_id: AssetId<T>, delegate: AccountId<T>, amount: AssetBalance<T>)
+		-> (), mutating, weighed::<T>(W::<T>::asset_approve);
+	/// Transfers `amount` of the asset from `owner` to `destination`, using an approval previously
+	/// given by `owner` to the contract.
+	106 => fn asset_transfer_approved(
+		asset_id: AssetId<T>,
+		owner: AccountId<T>,
+		destination: AccountId<T>,
+		amount: AssetBalance<T>
+	) -> (), mutating, weighed::<T>(W::<T>::asset_transfer_approved);
+	/// Returns the asset balance of `who`.
+	107 => fn asset_balance_of(asset_id: AssetId<T>, who: AccountId<T>) -> AssetBalance<T>, view,
+		weighed::<T>(W::<T>::asset_balance_of);
+	/// Returns the total issuance of the asset.
+	108 => fn asset_total_supply(asset_id: AssetId<T>) -> AssetBalance<T>, view,
+		weighed::<T>(W::<T>::asset_total_supply);
+	/// Returns the amount `delegate` is still allowed to transfer out of `owner`'s balance.
+	109 => fn asset_allowance(asset_id: AssetId<T>, owner: AccountId<T>, delegate: AccountId<T>)
+		-> AssetBalance<T>, view, weighed::<T>(W::<T>::asset_allowance);
+
+	/// Creates a new NFT class administered by the contract.
+	201 => fn nft_create_class(class: ClassId<T>) -> (), mutating,
+		weighed::<T>(W::<T>::nft_create_class);
+	/// Mints an NFT in a class administered by the contract. The contract owns the new instance
+	/// unless another `owner` is given.
+	202 => fn nft_mint(class: ClassId<T>, instance: InstanceId<T>, owner: Option<AccountId<T>>)
+		-> (), mutating, weighed::<T>(W::<T>::nft_mint);
+	/// Burns an NFT in a class administered by the contract.
+	203 => fn nft_burn(class: ClassId<T>, instance: InstanceId<T>) -> (), mutating,
+		weighed::<T>(W::<T>::nft_burn);
+	/// Transfers an NFT owned or administered by the contract to `dest`.
+	204 => fn nft_transfer(class: ClassId<T>, instance: InstanceId<T>, dest: AccountId<T>) -> (),
+		mutating, weighed::<T>(W::<T>::nft_transfer);
+	/// Sets an attribute on a class administered by the contract, or on one of its instances.
+	205 => fn nft_set_attribute(
+		class: ClassId<T>,
+		instance: Option<InstanceId<T>>,
+		key: Vec<u8>,
+		value: Vec<u8>
+	) -> (), mutating, weighed::<T>(W::<T>::nft_set_attribute);
+	/// Returns the owner of an NFT, if it exists.
+	206 => fn nft_owner_of(class: ClassId<T>, instance: InstanceId<T>) -> Option<AccountId<T>>,
+		view, weighed::<T>(W::<T>::nft_owner_of);
+	/// Returns an attribute of a class, or of one of its instances, if it is set.
+	207 => fn nft_attribute(class: ClassId<T>, instance: Option<InstanceId<T>>, key: Vec<u8>)
+		-> Option<Vec<u8>>, view, weighed::<T>(W::<T>::nft_attribute);
+
+	/// Dispatches a SCALE-encoded runtime call with the contract as the signed origin. Only calls
+	/// allowed by the runtime's call filter are dispatched.
+	301 => fn dispatch_call(call: Vec<u8>) -> (), mutating,
+		WeightFormula { plus_dispatched_call: true, ..weighed::<T>(W::<T>::dispatch_call) };
+	/// Dispatches a SCALE-encoded runtime call with the `pallet_template::ContractOrigin` of the
+	/// contract as origin, for calls requiring it to come from a given contract or contract code.
+	/// Only calls allowed by the runtime's call filter are dispatched.
+	302 => fn dispatch_call_as_contract(call: Vec<u8>) -> (), mutating,
+		WeightFormula { plus_dispatched_call: true, ..weighed::<T>(W::<T>::dispatch_call) };
+
+	/// Returns randomness derived from `subject`. The seed has been knowable to anyone, including
+	/// block authors, since the block it was determined at, and changes with every block. Only use
+	/// it to settle something committed to strictly before that block, with the block fixed when
+	/// committing, or whoever picks when to read it picks the seed.
+	401 => fn random(subject: Vec<u8>) -> RandomOutput<T::Hash, T::BlockNumber>, view,
+		weighed_per_input_byte::<T>(W::<T>::random, W::<T>::random_per_byte);
+
+	/// Verifies an sr25519 `signature` of `message` by `public`.
+	501 => fn sr25519_verify(
+		signature: sr25519::Signature,
+		message: Vec<u8>,
+		public: sr25519::Public
+	) -> bool, view,
+		weighed_per_input_byte::<T>(W::<T>::sr25519_verify, W::<T>::sr25519_verify_per_byte);
+	/// Verifies an ed25519 `signature` of `message` by `public`.
+	502 => fn ed25519_verify(
+		signature: ed25519::Signature,
+		message: Vec<u8>,
+		public: ed25519::Public
+	) -> bool, view,
+		weighed_per_input_byte::<T>(W::<T>::ed25519_verify, W::<T>::ed25519_verify_per_byte);
+	/// Verifies an ECDSA `signature` of the blake2-256 hash of `message` by the compressed public
+	/// key `public`.
+	503 => fn ecdsa_verify(signature: ecdsa::Signature, message: Vec<u8>, public: ecdsa::Public)
+		-> bool, view,
+		weighed_per_input_byte::<T>(W::<T>::ecdsa_verify, W::<T>::ecdsa_verify_per_byte);
+	/// Recovers the compressed ECDSA public key that signed the blake2-256 hash of `message`.
+	504 => fn ecdsa_recover(signature: ecdsa::Signature, message: Vec<u8>) -> Option<[u8; 33]>,
+		view, weighed_per_input_byte::<T>(W::<T>::ecdsa_recover, W::<T>::ecdsa_recover_per_byte);
+
+	/// Reads the raw value stored under `key` in runtime storage. Only keys starting with one of
+	/// the prefixes readable by contracts can be read.
+	601 => fn read_storage(key: Vec<u8>) -> Option<Vec<u8>>, view,
+		WeightFormula {
+			per_output_byte: slope(W::<T>::read_storage_per_byte),
+			..weighed::<T>(W::<T>::read_storage)
+		};
+
+	/// Makes the runtime deposit a typed `TemplateModule::ContractSignal` event carrying the
+	/// contract's address, `topic` and `payload`.
+	701 => fn emit_signal(topic: [u8; 32], payload: Vec<u8>) -> (), mutating,
+		weighed_per_input_byte::<T>(W::<T>::emit_signal, W::<T>::emit_signal_per_byte);
+	/// Subscribes the contract to the runtime events matching `filter`, a SCALE-encoded runtime
+	/// `EventFilter`, from the next block. The events of a block matching it are delivered at the
+	/// start of the next: the contract calls itself with each of them, its message with
+	/// `selector` getting the SCALE-encoded event as a `Vec<u8>`. Subscribing again with the same
+	/// `filter` replaces the selector. A deposit is reserved from the contract for each
+	/// subscription, returned when it unsubscribes, and traps the contract if it can't pay it.
+	702 => fn subscribe(filter: Vec<u8>, selector: [u8; 4]) -> (), mutating,
+		weighed::<T>(W::<T>::subscribe);
+	/// Removes the subscription of the contract to the runtime events matching `filter`, if any,
+	/// returning its deposit.
+	703 => fn unsubscribe(filter: Vec<u8>) -> (), mutating, weighed::<T>(W::<T>::unsubscribe);
+	/// Requests a result only available later, such as off-chain data, described by `payload` in
+	/// a format agreed on with the fulfillers, returning the request id. Once fulfilled, the
+	/// contract calls itself back: its message with `selector` gets the id and the result as
+	/// `(u64, Vec<u8>)` arguments, given `gas_limit`. The gas limit, at least the runtime's
+	/// `MinRequestGasLimit`, and the fulfilment are charged now. A deposit is reserved from the
+	/// contract until the request is fulfilled or, once `RequestTimeout` blocks passed, expired by
+	/// anyone. Traps the contract if it can't pay the deposit.
+	704 => fn request(selector: [u8; 4], gas_limit: u64, payload: Vec<u8>) -> u64, mutating,
+		weighed::<T>(W::<T>::request);
+	/// Returns the latest value aggregated by the oracle for `key`, the median of the values fed
+	/// for it which were not stale, and when it was aggregated in milliseconds since the Unix
+	/// epoch, or `None` if no value was ever fed for `key`.
+	705 => fn oracle_value(key: T::OracleKey) -> Option<TimestampedValue<T::OracleValue>>, view,
+		weighed::<T>(W::<T>::oracle_value);
+
+	/// Notes `encoded_proposal`, a SCALE-encoded runtime call, as a `Democracy` preimage. The
+	/// deposit is reserved from the contract. Only permitted contracts may call it.
+	801 => fn democracy_note_preimage(encoded_proposal: Vec<u8>) -> (), mutating,
+		WeightFormula { plus_dispatched_call: true, ..weighed::<T>(W::<T>::dispatch_call) };
+	/// Submits the proposal with preimage hash `proposal_hash` to `Democracy`, locking `value` of
+	/// the contract's balance as deposit. Only permitted contracts may call it.
+	802 => fn democracy_propose(proposal_hash: T::Hash, value: DemocracyBalance<T>) -> (),
+		mutating,
+		WeightFormula { plus_dispatched_call: true, ..weighed::<T>(W::<T>::dispatch_call) };
+	/// Seconds public proposal `proposal`, which has no more than `seconds_upper_bound` seconds,
+	/// with the contract's balance. Only permitted contracts may call it.
+	803 => fn democracy_second(proposal: u32, seconds_upper_bound: u32) -> (), mutating,
+		WeightFormula { plus_dispatched_call: true, ..weighed::<T>(W::<T>::dispatch_call) };
+	/// Votes on referendum `ref_index`, locking `balance` of the contract's balance with
+	/// `conviction`, from 0 (no lock) to 6. Only permitted contracts may call it.
+	804 => fn democracy_vote(
+		ref_index: u32,
+		aye: bool,
+		conviction: u8,
+		balance: DemocracyBalance<T>
+	) -> (), mutating,
+		WeightFormula { plus_dispatched_call: true, ..weighed::<T>(W::<T>::dispatch_call) };
+}
@@ -0,0 +1,64 @@
+//! Chain extension functions letting contracts take part in `pallet_democracy` with their own
+//! balance: noting a preimage, proposing it, seconding proposals and voting on referenda.
+//!
+//! Only the contracts in `Config::GovernanceContracts` may call them, others get
+//! `RetCode::NotPermitted`. The `pallet_democracy` calls are dispatched with the contract's own
+//! account as the signed origin, so deposits are reserved from and votes lock the contract's
+//! balance. Like any signed call they pass the runtime's `BaseCallFilter`, but not the chain
+//! extension's `CallFilter`, which only applies to the `do_dispatch_call` functions. As with
+//! `do_dispatch_call`, a failing call is reported as `RetCode::DispatchFailed` instead of trapping.
+
+use super::{dispatch::dispatch_as, env::ExtensionEnv, Config, RetCode};
+use frame_support::traits::{Contains, Currency, Get};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::SysConfig;
+use pallet_democracy::{AccountVote, Conviction, Vote};
+use sp_runtime::DispatchError;
+use sp_std::{convert::TryFrom, vec::Vec};
+
+type DemocracyBalance<T> =
+	<<T as pallet_democracy::Config>::Currency as Currency<<T as SysConfig>::AccountId>>::Balance;
+
+pub(super) fn call<T, E>(func_id: u32, env: &mut E) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_democracy::Config + Config,
+	<T as pallet_contracts::Config>::Call: From<pallet_democracy::Call<T>>,
+	E: ExtensionEnv<T>,
+{
+	// Reading the permitted contracts is not part of the benchmarked weight
+	env.charge_weight(T::DbWeight::get().reads(1))?;
+	let contract = env.address();
+	if !<T as Config>::GovernanceContracts::contains(&contract) {
+		return Ok(RetCode::NotPermitted)
+	}
+
+	let call = match func_id {
+		// do_democracy_note_preimage
+		801 => {
+			let encoded_proposal: Vec<u8> = env.read_as()?;
+			pallet_democracy::Call::<T>::note_preimage { encoded_proposal }
+		},
+		// do_democracy_propose
+		802 => {
+			let (proposal_hash, value): (T::Hash, DemocracyBalance<T>) = env.read_as()?;
+			pallet_democracy::Call::<T>::propose { proposal_hash, value }
+		},
+		// do_democracy_second
+		803 => {
+			let (proposal, seconds_upper_bound): (u32, u32) = env.read_as()?;
+			pallet_democracy::Call::<T>::second { proposal, seconds_upper_bound }
+		},
+		// do_democracy_vote
+		804 => {
+			let (ref_index, aye, conviction, balance): (u32, bool, u8, DemocracyBalance<T>) =
+				env.read_as()?;
+			let conviction = Conviction::try_from(conviction)
+				.map_err(|_| pallet_contracts::Error::<T>::DecodingFailed)?;
+			let vote = AccountVote::Standard { vote: Vote { aye, conviction }, balance };
+			pallet_democracy::Call::<T>::vote { ref_index, vote }
+		},
+		_ => return Err(DispatchError::Other("Unimplemented func_id")),
+	};
+
+	dispatch_as::<T, E>(env, call.into(), RawOrigin::Signed(contract).into())
+}
@@ -0,0 +1,143 @@
+//! Metadata describing the chain extension functions to clients, returned SCALE-encoded by
+//! `ChainExtensionApi::metadata`.
+
+use codec::Encode;
+use frame_support::weights::Weight;
+use scale_info::{
+	form::{Form, MetaForm, PortableForm},
+	IntoPortable, PortableRegistry, Registry,
+};
+use sp_std::vec::Vec;
+
+/// Metadata of the chain extension functions, together with the types they take and return.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct ExtensionMetadata {
+	/// Registry of the types referenced by `functions`.
+	pub types: PortableRegistry,
+	/// The chain extension functions, ordered by id, then version.
+	pub functions: Vec<FunctionMetadata<PortableForm>>,
+	/// The status codes returned by the functions, ordered by code.
+	pub ret_codes: Vec<RetCodeMetadata<PortableForm>>,
+}
+
+impl ExtensionMetadata {
+	/// Register the types referenced by `functions` and convert them to their portable form.
+	pub fn new(functions: Vec<FunctionMetadata>, ret_codes: Vec<RetCodeMetadata>) -> Self {
+		let mut registry = Registry::new();
+		let functions = registry.map_into_portable(functions);
+		let ret_codes = registry.map_into_portable(ret_codes);
+		Self { types: registry.into(), functions, ret_codes }
+	}
+}
+
+/// Metadata of a single chain extension function.
+///
+/// Every function also returns a status code, see `RetCode`. `output` is the type written to the
+/// contract's output buffer, `()` if the function only returns a status code.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct FunctionMetadata<T: Form = MetaForm> {
+	/// The `func_id` contracts call the function with. Its upper 16 bits are the version of the
+	/// function, see `versioned`.
+	pub id: u32,
+	/// Name of the function.
+	pub name: T::String,
+	/// Documentation of the function.
+	pub docs: Vec<T::String>,
+	/// Arguments of the function. The input the function reads is the SCALE-encoded tuple of their
+	/// values, or the value itself for a single argument.
+	pub args: Vec<ArgMetadata<T>>,
+	/// Type of the SCALE-encoded output the function writes.
+	pub output: T::Type,
+	/// Whether the function changes state. Such functions are refused in read-only queries.
+	pub mutating: bool,
+	/// How the function is weighed.
+	pub weight: WeightFormula,
+	/// Whether the function is a deprecated version, refused once the runtime retires it.
+	pub deprecated: bool,
+}
+
+impl IntoPortable for FunctionMetadata {
+	type Output = FunctionMetadata<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		FunctionMetadata {
+			id: self.id,
+			name: self.name.into_portable(registry),
+			docs: registry.map_into_portable(self.docs),
+			args: registry.map_into_portable(self.args),
+			output: registry.register_type(&self.output),
+			mutating: self.mutating,
+			weight: self.weight,
+			deprecated: self.deprecated,
+		}
+	}
+}
+
+/// Metadata of an argument of a chain extension function.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct ArgMetadata<T: Form = MetaForm> {
+	/// Name of the argument.
+	pub name: T::String,
+	/// Type of the argument.
+	pub ty: T::Type,
+}
+
+impl IntoPortable for ArgMetadata {
+	type Output = ArgMetadata<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		ArgMetadata {
+			name: self.name.into_portable(registry),
+			ty: registry.register_type(&self.ty),
+		}
+	}
+}
+
+/// Metadata of a status code returned by the chain extension functions, see `RetCode`.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct RetCodeMetadata<T: Form = MetaForm> {
+	/// The status code.
+	pub code: u32,
+	/// Name of the status code.
+	pub name: T::String,
+	/// Documentation of the status code.
+	pub docs: Vec<T::String>,
+}
+
+impl IntoPortable for RetCodeMetadata {
+	type Output = RetCodeMetadata<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		RetCodeMetadata {
+			code: self.code,
+			name: self.name.into_portable(registry),
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
+
+/// Weight charged for a call of a chain extension function, as a linear formula of the lengths of
+/// its input and output.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Encode, Debug)]
+pub struct WeightFormula {
+	/// Weight charged for every call.
+	pub base: Weight,
+	/// Weight charged per byte of input, before it is read.
+	pub per_input_byte: Weight,
+	/// Weight charged per byte of output, before it is written.
+	pub per_output_byte: Weight,
+	/// Whether the weight of the runtime call dispatched by the function is charged on top.
+	pub plus_dispatched_call: bool,
+}
+
+impl WeightFormula {
+	/// Weight charged before a call with `input_len` bytes of input is executed.
+	pub fn before_call(&self, input_len: u32) -> Weight {
+		self.base.saturating_add(self.per_input_byte.saturating_mul(input_len.into()))
+	}
+
+	/// Weight charged before `output_len` bytes of output are written.
+	pub fn before_output(&self, output_len: u32) -> Weight {
+		self.per_output_byte.saturating_mul(output_len.into())
+	}
+}
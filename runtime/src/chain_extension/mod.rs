@@ -0,0 +1,341 @@
+use codec::Codec;
+use frame_support::{log::error, traits::Contains};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_contracts_primitives::{Code, ContractExecResult, ContractInstantiateResult};
+use sp_runtime::{traits::StaticLookup, DispatchError};
+use sp_std::vec::Vec;
+use crate::{
+	sp_api_hidden_includes_construct_runtime::hidden_include::traits::Get, Encode, TemplateModule,
+};
+
+mod assets;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "std")]
+pub mod bindings;
+#[cfg(test)]
+mod contract_tests;
+mod crypto;
+mod dispatch;
+mod env;
+mod functions;
+mod governance;
+pub mod metadata;
+mod oracle;
+mod randomness;
+mod request;
+mod signal;
+mod storage;
+mod subscription;
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod testing;
+mod uniques;
+pub mod weights;
+
+pub use env::ExtensionEnv;
+pub use weights::WeightInfo as ChainExtensionWeightInfo;
+
+/// The `func_id` contracts call version `version` of function `id` with. Functions are versioned
+/// so that their types can change without breaking deployed contracts: those keep calling the
+/// deprecated version, which the runtime serves by adapting its encoding, until it is retired.
+/// Version 0 is called by `id` alone.
+pub const fn versioned(version: u16, id: u16) -> u32 {
+	(version as u32) << 16 | id as u32
+}
+
+/// Longest input accepted by any chain extension function, whatever `Config::max_input_len`
+/// allows. Also the upper bound of the benchmarks of input-sized weights.
+pub const MAX_INPUT_LEN: u32 = 16 * 1024;
+
+/// Runtime-specific configuration of `ExampleExtension`.
+pub trait Config: SysConfig {
+	/// Prefixes of the storage keys contracts may read with `do_read_storage`.
+	type ReadableStoragePrefixes: Get<Vec<Vec<u8>>>;
+	/// Runtime calls contracts may dispatch with `do_dispatch_call` and
+	/// `do_dispatch_call_as_contract`. Separate from `pallet_contracts::Config::CallFilter`, which
+	/// gates `seal_call_runtime`.
+	type CallFilter: Contains<Self::Call>;
+	/// Contracts allowed to take part in `pallet_democracy` through the governance functions.
+	type GovernanceContracts: Contains<Self::AccountId>;
+	/// Weights of the chain extension functions, benchmarked in `benchmarking.rs`.
+	type WeightInfo: ChainExtensionWeightInfo;
+
+	/// Longest input, in bytes, accepted by function `func_id`. Longer inputs are refused with
+	/// `RetCode::InputTooLarge` before they are read.
+	fn max_input_len(func_id: u32) -> u32;
+
+	/// Block from which the deprecated function `func_id` is refused with `RetCode::Retired`, if
+	/// any. Until then it is served, with a warning in the debug output of the call.
+	fn retired_from(func_id: u32) -> Option<Self::BlockNumber>;
+}
+
+environmental::environmental!(read_only_context: bool);
+
+/// Execute `f` as a read-only query, such as a dry-run through `ContractsQueryApi`.
+/// Chain extension functions that change state are refused with `RetCode::ReadOnlyContext` rather
+/// than executed, so contracts can tell they are being queried.
+pub fn read_only<R>(f: impl FnOnce() -> R) -> R {
+	read_only_context::using(&mut true, f)
+}
+
+fn is_read_only() -> bool {
+	read_only_context::with(|read_only| *read_only).unwrap_or(false)
+}
+
+macro_rules! ret_codes {
+	($( $( #[doc = $doc:literal] )* $name:ident = $code:literal, )*) => {
+		/// Status codes returned to the contract for failures it is expected to handle itself. Any
+		/// other failure traps the calling contract with a `DispatchError`.
+		#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+		#[repr(u32)]
+		pub enum RetCode {
+			$( $( #[doc = $doc] )* $name = $code, )*
+		}
+
+		impl RetCode {
+			/// Metadata of every status code, ordered by code.
+			pub fn metadata() -> Vec<metadata::RetCodeMetadata> {
+				sp_std::vec![$(
+					metadata::RetCodeMetadata {
+						code: $code,
+						name: stringify!($name),
+						docs: sp_std::vec![$( $doc ),*],
+					},
+				)*]
+			}
+		}
+	};
+}
+
+ret_codes! {
+	/// The function completed successfully.
+	Success = 0,
+	/// The runtime call passed to the function could not be decoded.
+	InvalidCall = 1,
+	/// The runtime call is not allowed by the runtime's call filter.
+	CallFiltered = 2,
+	/// The runtime call was dispatched but returned an error.
+	DispatchFailed = 3,
+	/// The storage key does not start with any of the prefixes contracts may read.
+	StorageKeyNotAllowed = 4,
+	/// The signal payload is longer than `pallet_template::Config::MaxSignalPayloadLen`.
+	SignalPayloadTooLarge = 5,
+	/// The input is longer than `Config::max_input_len` allows for the function.
+	InputTooLarge = 6,
+	/// The function changes state but the contract is executed as a read-only query.
+	ReadOnlyContext = 7,
+	/// The function is a deprecated version the runtime no longer serves.
+	Retired = 8,
+	/// The contract is not allowed to call the function.
+	NotPermitted = 9,
+	/// The contract, or all contracts together, have as many event subscriptions as allowed.
+	TooManySubscriptions = 10,
+	/// The contract, or all contracts together, have as many requests waiting to be fulfilled as
+	/// allowed.
+	TooManyRequests = 11,
+}
+
+impl From<RetCode> for RetVal {
+	fn from(code: RetCode) -> Self {
+		RetVal::Converging(code as u32)
+	}
+}
+
+/// Metadata of the chain extension functions, as returned by `ChainExtensionApi`.
+pub fn extension_metadata<T>() -> metadata::ExtensionMetadata
+where
+	T: Config
+		+ pallet_template::Config
+		+ pallet_balances::Config
+		+ pallet_assets::Config
+		+ pallet_uniques::Config
+		+ pallet_democracy::Config,
+{
+	metadata::ExtensionMetadata::new(functions::metadata::<T>(), RetCode::metadata())
+}
+
+sp_api::decl_runtime_apis! {
+	/// Describes the chain extension functions available to contracts.
+	pub trait ChainExtensionApi {
+		/// SCALE-encoded `metadata::ExtensionMetadata` of the chain extension functions.
+		fn metadata() -> Vec<u8>;
+	}
+
+	/// Dry-runs of contract calls and instantiations as read-only queries, in which chain
+	/// extension functions that change state are refused with `RetCode::ReadOnlyContext`. The
+	/// dry-runs of `pallet_contracts`' own `ContractsApi` execute these functions, so they can
+	/// estimate the gas of messages changing state.
+	pub trait ContractsQueryApi<AccountId, Balance, Hash> where
+		AccountId: Codec,
+		Balance: Codec,
+		Hash: Codec,
+	{
+		/// Query a contract as `ContractsApi::call` calls it.
+		fn query_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			input_data: Vec<u8>,
+		) -> ContractExecResult;
+
+		/// Query the instantiation of a contract as `ContractsApi::instantiate` instantiates it.
+		fn query_instantiate(
+			origin: AccountId,
+			endowment: Balance,
+			gas_limit: u64,
+			code: Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> ContractInstantiateResult<AccountId>;
+	}
+}
+
+pub struct ExampleExtension;
+
+impl<T> ChainExtension<T> for ExampleExtension
+where
+	T: SysConfig
+		+ pallet_contracts::Config
+		+ pallet_template::Config
+		+ pallet_balances::Config
+		+ pallet_assets::Config
+		+ pallet_uniques::Config
+		+ pallet_randomness_collective_flip::Config
+		+ pallet_democracy::Config
+		+ Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	<T as SysConfig>::Origin: From<pallet_template::Origin<T>>,
+	<T as pallet_contracts::Config>::Call: From<pallet_democracy::Call<T>>,
+{
+	fn call<E: Ext>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		Self::dispatch::<T, _>(func_id, &mut env.buf_in_buf_out())
+	}
+}
+
+impl ExampleExtension {
+	/// Execute function `func_id` in `env`, as `ChainExtension::call` does for contracts.
+	pub fn dispatch<T, E>(func_id: u32, env: &mut E) -> Result<RetVal, DispatchError>
+	where
+		T: SysConfig
+			+ pallet_contracts::Config
+			+ pallet_template::Config
+			+ pallet_balances::Config
+			+ pallet_assets::Config
+			+ pallet_uniques::Config
+			+ pallet_randomness_collective_flip::Config
+			+ pallet_democracy::Config
+			+ Config,
+		<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+		<T as SysConfig>::Origin: From<pallet_template::Origin<T>>,
+		<T as pallet_contracts::Config>::Call: From<pallet_democracy::Call<T>>,
+		E: ExtensionEnv<T>,
+	{
+		let function = functions::lookup::<T>(func_id).ok_or_else(|| {
+			error!("Called an unregistered `func_id`: {:}", func_id);
+			DispatchError::Other("Unimplemented func_id")
+		})?;
+
+		if let Some(warning) = function.deprecation {
+			let now = frame_system::Pallet::<T>::block_number();
+			if <T as Config>::retired_from(func_id).map_or(false, |block| now >= block) {
+				return Ok(RetCode::Retired.into())
+			}
+			env.append_debug_buffer(warning);
+		}
+
+		if function.mutating && is_read_only() {
+			return Ok(RetCode::ReadOnlyContext.into())
+		}
+
+		// Refuse oversized input and charge for it before anything is decoded
+		let in_len = env.in_len();
+		if in_len > <T as Config>::max_input_len(func_id).min(MAX_INPUT_LEN) {
+			return Ok(RetCode::InputTooLarge.into())
+		}
+		env.charge_weight(function.weight.before_call(in_len))?;
+
+		// Match on function id assigned in the contract
+		match func_id {
+			// do_store_in_runtime
+			1 => {
+				// retrieve argument that was passed in smart contract invocation
+				let value: u32 = env.read_as()?;
+				let caller = env.caller();
+
+				crate::pallet_template::Pallet::<T>::insert_number(
+					RawOrigin::Signed(caller).into(),
+					value,
+				)?;
+			},
+			// do_balance_transfer
+			2 | functions::BALANCE_TRANSFER_V1 => {
+				// Retrieve arguments. The `u32` amount of version 0 is adapted to the `Balance` of
+				// version 1.
+				let (transfer_amount, recipient_account): (
+					<T as pallet_balances::Config>::Balance,
+					T::AccountId,
+				) = if func_id == functions::BALANCE_TRANSFER_V1 {
+					env.read_as()?
+				} else {
+					let (amount, recipient): (u32, T::AccountId) = env.read_as()?;
+					(amount.into(), recipient)
+				};
+				let recipient = T::Lookup::unlookup(recipient_account);
+				let caller = env.caller();
+
+				pallet_balances::Pallet::<T>::transfer(
+					RawOrigin::Signed(caller).into(),
+					recipient,
+					transfer_amount,
+				)
+				.map_err(|d| d.error)?;
+			},
+			// do_get_balance
+			3 => {
+				let account: T::AccountId = env.read_as()?;
+				let result = pallet_balances::Pallet::<T>::free_balance(account).encode();
+
+				env.write(&result).map_err(|_| "Encountered an error when querying balance.")?;
+			},
+			// do_get_from_runtime
+			4 => {
+				let result = TemplateModule::get_value().encode();
+				env.write(&result)
+					.map_err(|_| "Encountered an error when retrieving runtime storage value.")?;
+			},
+			// pallet_assets functions, see `assets.rs`
+			101..=109 => assets::call::<T, E>(func_id, env)?,
+			// pallet_uniques functions, see `uniques.rs`
+			201..=207 => uniques::call::<T, E>(func_id, env)?,
+			// runtime call dispatching functions, see `dispatch.rs`
+			301 | 302 => return dispatch::call::<T, E>(func_id, env).map(Into::into),
+			// do_random, see `randomness.rs`
+			401 => randomness::call::<T, E>(env)?,
+			// signature verification functions, see `crypto.rs`
+			501..=504 => crypto::call::<T, E>(func_id, env)?,
+			// do_read_storage, see `storage.rs`
+			601 => return storage::call::<T, E>(env, &function.weight).map(Into::into),
+			// do_emit_signal, see `signal.rs`
+			701 => return signal::call::<T, E>(env).map(Into::into),
+			// event subscription functions, see `subscription.rs`
+			702 | 703 => return subscription::call::<T, E>(func_id, env).map(Into::into),
+			// do_request, see `request.rs`
+			704 => return request::call::<T, E>(env).map(Into::into),
+			// do_oracle_value, see `oracle.rs`
+			705 => oracle::call::<T, E>(env)?,
+			// pallet_democracy functions, see `governance.rs`
+			801..=804 => return governance::call::<T, E>(func_id, env).map(Into::into),
+			_ => return Err(DispatchError::Other("Unimplemented func_id")),
+		}
+		// No error, return status code `0`, indicating `Ok(())`
+		Ok(RetCode::Success.into())
+	}
+}
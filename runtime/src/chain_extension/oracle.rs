@@ -0,0 +1,20 @@
+//! Chain extension function giving contracts the values aggregated by `pallet_template`'s oracle.
+
+use super::env::ExtensionEnv;
+use codec::Encode;
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::DispatchError;
+
+pub(super) fn call<T, E>(env: &mut E) -> Result<(), DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_template::Config,
+	E: ExtensionEnv<T>,
+{
+	let key: T::OracleKey = env.read_as()?;
+	let result = pallet_template::Pallet::<T>::oracle_value(key).encode();
+
+	env.write(&result)
+		.map_err(|_| "Encountered an error when writing the oracle value.")?;
+
+	Ok(())
+}
@@ -0,0 +1,41 @@
+//! Chain extension function giving contracts access to `pallet_randomness_collective_flip`.
+
+use super::env::ExtensionEnv;
+use codec::Encode;
+use frame_support::traits::{Get, Randomness};
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+/// Output of `do_random`, written to the contract's output buffer.
+#[derive(Encode, scale_info::TypeInfo)]
+pub struct RandomOutput<Hash, BlockNumber> {
+	/// Random value derived from the contract-provided subject.
+	pub seed: Hash,
+	/// The block at which `seed` was determined. `seed` has been knowable to anyone since that
+	/// block, so it may only settle what was committed to before it. It also changes with every
+	/// block, so the block it is taken from must be fixed when committing. E.g. a bet placed at
+	/// block `n` is settled with the seed determined at `n + 1`, and expires once that has passed.
+	pub determined_at: BlockNumber,
+}
+
+pub(super) fn call<T, E>(env: &mut E) -> Result<(), DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_randomness_collective_flip::Config,
+	E: ExtensionEnv<T>,
+{
+	let subject: Vec<u8> = env.read_as()?;
+	// Same subject limit as the `seal_random` host function, which reads the same source
+	let subject_len = <T as pallet_contracts::Config>::Schedule::get().limits.subject_len;
+	if subject.len() > subject_len as usize {
+		return Err(DispatchError::Other("Randomness subject is too long."))
+	}
+
+	let (seed, determined_at) = pallet_randomness_collective_flip::Pallet::<T>::random(&subject);
+	let result = RandomOutput { seed, determined_at }.encode();
+
+	env.write(&result)
+		.map_err(|_| "Encountered an error when writing randomness.")?;
+
+	Ok(())
+}
@@ -0,0 +1,40 @@
+//! Chain extension function letting contracts request a result only available later, such as
+//! off-chain data, from `pallet_template`.
+//!
+//! The request is fulfilled by the runtime's `pallet_template::Config::FulfilOrigin`, with the
+//! `fulfil_request` extrinsic, which makes the contract call itself back with the request id and
+//! the SCALE-encoded result as `(u64, Vec<u8>)` arguments. Fulfilling is free for the fulfiller:
+//! the weight of `fulfil_request` with the longest result and the gas limit of the call back are
+//! charged to the contract when requesting, as the fulfilling transaction is not its caller's.
+
+use super::{env::ExtensionEnv, RetCode};
+use codec::Encode;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_contracts::chain_extension::SysConfig;
+use pallet_template::WeightInfo;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub(super) fn call<T, E>(env: &mut E) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_template::Config,
+	E: ExtensionEnv<T>,
+{
+	let (selector, gas_limit, payload): ([u8; 4], Weight, Vec<u8>) = env.read_as()?;
+	let max_result_len = <T as pallet_template::Config>::MaxRequestResultLen::get();
+	env.charge_weight(
+		<T as pallet_template::Config>::WeightInfo::fulfil_request(max_result_len)
+			.saturating_add(gas_limit),
+	)?;
+	let contract = env.address();
+
+	match pallet_template::Pallet::<T>::request(contract, selector, gas_limit, payload) {
+		Ok(id) => {
+			env.write(&id.encode())?;
+			Ok(RetCode::Success)
+		},
+		Err(pallet_template::Error::<T>::RequestPayloadTooLarge) => Ok(RetCode::InputTooLarge),
+		Err(pallet_template::Error::<T>::TooManyRequests) => Ok(RetCode::TooManyRequests),
+		Err(e) => Err(e.into()),
+	}
+}
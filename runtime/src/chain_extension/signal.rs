@@ -0,0 +1,24 @@
+//! Chain extension function letting contracts deposit a typed `ContractSignal` runtime event
+//! through `pallet_template`, which other pallets and standard indexers can react to without
+//! knowing the contract's ink! metadata.
+
+use super::{env::ExtensionEnv, RetCode};
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub(super) fn call<T, E>(env: &mut E) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_template::Config,
+	E: ExtensionEnv<T>,
+{
+	let (topic, payload): ([u8; 32], Vec<u8>) = env.read_as()?;
+	let contract = env.address();
+
+	match pallet_template::Pallet::<T>::signal_from_contract(contract, topic, payload) {
+		Ok(()) => Ok(RetCode::Success),
+		Err(pallet_template::Error::<T>::SignalPayloadTooLarge) =>
+			Ok(RetCode::SignalPayloadTooLarge),
+		Err(e) => Err(e.into()),
+	}
+}
@@ -0,0 +1,38 @@
+//! Chain extension function giving contracts read-only access to whitelisted runtime storage.
+//!
+//! The contract passes the full storage key. The raw value is returned undecoded if the key starts
+//! with one of the prefixes in `Config::ReadableStoragePrefixes`, so new storage items can be
+//! exposed to contracts by configuration rather than with a new function.
+
+use super::{env::ExtensionEnv, metadata::WeightFormula, Config, RetCode};
+use codec::Encode;
+use frame_support::{storage::unhashed, traits::Get};
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+/// Longest storage key accepted, enough for a double map keyed by two account ids hashed with
+/// `Blake2_128Concat`.
+pub const MAX_STORAGE_KEY_LEN: usize = 128;
+
+pub(super) fn call<T, E>(env: &mut E, weight: &WeightFormula) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + Config,
+	E: ExtensionEnv<T>,
+{
+	let key: Vec<u8> = env.read_as()?;
+	if key.len() > MAX_STORAGE_KEY_LEN {
+		return Err(DispatchError::Other("Storage key is too long."))
+	}
+	if !T::ReadableStoragePrefixes::get().iter().any(|prefix| key.starts_with(prefix)) {
+		return Ok(RetCode::StorageKeyNotAllowed)
+	}
+
+	let result = unhashed::get_raw(&key).encode();
+	// The value is charged by its length as it is copied into the contract
+	env.charge_weight(weight.before_output(result.len() as u32))?;
+	env.write(&result)
+		.map_err(|_| "Encountered an error when reading runtime storage.")?;
+
+	Ok(RetCode::Success)
+}
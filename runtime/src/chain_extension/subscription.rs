@@ -0,0 +1,52 @@
+//! Chain extension functions letting contracts subscribe to runtime events through
+//! `pallet_template`, which calls them with each matching event at the end of the block.
+//!
+//! The events are described by the runtime's `pallet_template::Config::EventFilter`, passed
+//! SCALE-encoded. The subscribed message is called by the contract itself, with the SCALE-encoded
+//! runtime event as a `Vec<u8>` argument.
+
+use super::{env::ExtensionEnv, RetCode};
+use codec::Decode;
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub(super) fn call<T, E>(func_id: u32, env: &mut E) -> Result<RetCode, DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_template::Config,
+	E: ExtensionEnv<T>,
+{
+	let contract = env.address();
+	let result = match func_id {
+		// do_subscribe
+		702 => {
+			let (filter, selector): (Vec<u8>, [u8; 4]) = env.read_as()?;
+			pallet_template::Pallet::<T>::subscribe(
+				contract,
+				decode_filter::<T>(&filter)?,
+				selector,
+			)
+		},
+		// do_unsubscribe
+		703 => {
+			let filter: Vec<u8> = env.read_as()?;
+			pallet_template::Pallet::<T>::unsubscribe(contract, decode_filter::<T>(&filter)?)
+		},
+		_ => return Err(DispatchError::Other("Unimplemented func_id")),
+	};
+
+	match result {
+		Ok(()) => Ok(RetCode::Success),
+		Err(pallet_template::Error::<T>::TooManySubscriptions) => Ok(RetCode::TooManySubscriptions),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Decode the `EventFilter` passed by the contract, trapping it if invalid like any other input.
+fn decode_filter<T>(filter: &[u8]) -> Result<T::EventFilter, DispatchError>
+where
+	T: pallet_template::Config,
+{
+	T::EventFilter::decode(&mut &filter[..])
+		.map_err(|_| pallet_contracts::Error::<T>::DecodingFailed.into())
+}
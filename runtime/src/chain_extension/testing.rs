@@ -0,0 +1,375 @@
+//! Unit-testing of the chain extension functions without compiling and deploying a contract.
+//!
+//! `MockEnvironment` stands in for the `Environment` of a contract call, with a given caller,
+//! contract address, input and weight limit. `MockEnvironment::call` executes any `func_id` as
+//! `ExampleExtension` does for contracts, after which the output, the weight charged and the
+//! events deposited can be asserted on. Functions are called within `new_test_ext`.
+//!
+//! Also used by the fuzz targets in `runtime/fuzz`, with the `fuzzing` feature.
+
+use super::{env::ExtensionEnv, ExampleExtension};
+use crate::{AccountId, Balance, Event, Runtime, System};
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use pallet_contracts::chain_extension::RetVal;
+use sp_runtime::{BuildStorage, DispatchError};
+
+/// Externalities with `balances` endowed, at block 1 so that events are deposited.
+pub fn new_test_ext(balances: Vec<(AccountId, Balance)>) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Events deposited so far, oldest first.
+pub fn events() -> Vec<Event> {
+	System::events().into_iter().map(|record| record.event).collect()
+}
+
+/// The `Environment` of a contract at `address` called by `caller`, calling the chain extension.
+pub struct MockEnvironment {
+	/// Account calling the contract.
+	pub caller: AccountId,
+	/// Account of the contract.
+	pub address: AccountId,
+	/// Input passed by the contract.
+	pub input: Vec<u8>,
+	/// Weight left to the call, charging more fails with `OutOfGas`.
+	pub weight_limit: Weight,
+	/// Weight charged so far.
+	pub charged: Weight,
+	/// Output written by the last function called.
+	pub output: Vec<u8>,
+	/// Debug output appended by the functions called.
+	pub debug_buffer: String,
+}
+
+impl MockEnvironment {
+	/// A call without input nor weight limit.
+	pub fn new(caller: AccountId, address: AccountId) -> Self {
+		Self {
+			caller,
+			address,
+			input: Vec::new(),
+			weight_limit: Weight::max_value(),
+			charged: 0,
+			output: Vec::new(),
+			debug_buffer: String::new(),
+		}
+	}
+
+	/// Pass the SCALE-encoded `input` to the functions called.
+	pub fn input(mut self, input: impl Encode) -> Self {
+		self.input = input.encode();
+		self
+	}
+
+	/// Limit the weight the functions called may charge.
+	pub fn weight_limit(mut self, weight_limit: Weight) -> Self {
+		self.weight_limit = weight_limit;
+		self
+	}
+
+	/// Execute function `func_id`, returning its status code.
+	pub fn call(&mut self, func_id: u32) -> Result<u32, DispatchError> {
+		match ExampleExtension::dispatch::<Runtime, _>(func_id, self)? {
+			RetVal::Converging(code) => Ok(code),
+			RetVal::Diverging { .. } => unreachable!("the functions always return; qed"),
+		}
+	}
+
+	/// Decode the output written by the last function called.
+	pub fn output_as<V: Decode>(&self) -> V {
+		V::decode(&mut &self.output[..]).expect("the output is of the function's type")
+	}
+}
+
+impl ExtensionEnv<Runtime> for MockEnvironment {
+	fn in_len(&self) -> u32 {
+		self.input.len() as u32
+	}
+
+	fn read_as<V: Decode>(&mut self) -> Result<V, DispatchError> {
+		V::decode(&mut &self.input[..])
+			.map_err(|_| pallet_contracts::Error::<Runtime>::DecodingFailed.into())
+	}
+
+	fn write(&mut self, output: &[u8]) -> Result<(), DispatchError> {
+		self.output = output.to_vec();
+		Ok(())
+	}
+
+	fn charge_weight(&mut self, weight: Weight) -> Result<(), DispatchError> {
+		let charged = self.charged.saturating_add(weight);
+		if charged > self.weight_limit {
+			return Err(pallet_contracts::Error::<Runtime>::OutOfGas.into())
+		}
+		self.charged = charged;
+		Ok(())
+	}
+
+	fn charge_weight_then_adjust<R>(
+		&mut self,
+		weight: Weight,
+		f: impl FnOnce() -> (R, Weight),
+	) -> Result<R, DispatchError> {
+		self.charge_weight(weight)?;
+		let (result, actual_weight) = f();
+		// Like `pallet_contracts`, only refund what was charged in excess
+		self.charged -= weight.saturating_sub(actual_weight);
+		Ok(result)
+	}
+
+	fn caller(&mut self) -> AccountId {
+		self.caller.clone()
+	}
+
+	fn address(&mut self) -> AccountId {
+		self.address.clone()
+	}
+
+	fn append_debug_buffer(&mut self, msg: &str) -> bool {
+		self.debug_buffer.push_str(msg);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		chain_extension::{functions, RetCode},
+		Assets, Balances, Call, Democracy, MaxRequestResultLen, MinRequestGasLimit, Origin,
+		RequestDeposit, TemplateModule, Timestamp, DAYS, UNIT,
+	};
+	use frame_support::{assert_ok, traits::Contains};
+	use pallet_template::{Permission, TimestampedValue, WeightInfo};
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	const ALICE: AccountId = AccountId::new([1u8; 32]);
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+	const CONTRACT: AccountId = AccountId::new([3u8; 32]);
+
+	fn weight_before_call(func_id: u32, input_len: usize) -> Weight {
+		functions::lookup::<Runtime>(func_id)
+			.unwrap()
+			.weight
+			.before_call(input_len as u32)
+	}
+
+	#[test]
+	fn store_in_runtime_stores_value_as_caller() {
+		new_test_ext(vec![]).execute_with(|| {
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(42u32);
+
+			assert_eq!(env.call(1), Ok(RetCode::Success as u32));
+			assert_eq!(TemplateModule::get_value(), 42);
+			assert_eq!(
+				events(),
+				vec![Event::TemplateModule(pallet_template::Event::CalledPalletFromContract(42))],
+			);
+			assert_eq!(env.charged, weight_before_call(1, 4));
+		});
+	}
+
+	#[test]
+	fn balance_transfer_moves_funds_of_caller() {
+		new_test_ext(vec![(ALICE, 10_000)]).execute_with(|| {
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input((1_000u128, BOB));
+
+			assert_eq!(env.call(functions::BALANCE_TRANSFER_V1), Ok(RetCode::Success as u32));
+			assert_eq!(Balances::free_balance(&ALICE), 9_000);
+			assert_eq!(Balances::free_balance(&BOB), 1_000);
+			assert!(events()
+				.contains(&Event::Balances(pallet_balances::Event::Transfer(ALICE, BOB, 1_000))));
+		});
+	}
+
+	#[test]
+	fn get_balance_writes_free_balance() {
+		new_test_ext(vec![(BOB, 5_000)]).execute_with(|| {
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(BOB);
+
+			assert_eq!(env.call(3), Ok(RetCode::Success as u32));
+			assert_eq!(env.output_as::<Balance>(), 5_000);
+		});
+	}
+
+	#[test]
+	fn dispatch_call_dispatches_only_calls_allowed_by_its_own_filter() {
+		new_test_ext(vec![(CONTRACT, 10_000)]).execute_with(|| {
+			let transfer =
+				Call::Balances(pallet_balances::Call::transfer { dest: BOB.into(), value: 1_000 });
+			// `seal_call_runtime` keeps refusing every call
+			assert!(!<Runtime as pallet_contracts::Config>::CallFilter::contains(&transfer));
+
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(transfer.encode());
+			assert_eq!(env.call(301), Ok(RetCode::Success as u32));
+			assert_eq!(Balances::free_balance(&BOB), 1_000);
+
+			let remark = Call::System(frame_system::Call::remark { remark: vec![1, 2, 3] });
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(remark.encode());
+			assert_eq!(env.call(301), Ok(RetCode::CallFiltered as u32));
+		});
+	}
+
+	#[test]
+	fn asset_functions_move_only_assets_of_the_contract_or_approved_to_it() {
+		let balances = vec![(ALICE, 1_000 * UNIT), (BOB, UNIT), (CONTRACT, 1_000 * UNIT)];
+		new_test_ext(balances).execute_with(|| {
+			let call = |func_id: u32, input: &dyn Encode| {
+				let mut env = MockEnvironment::new(ALICE, CONTRACT).input(input.encode());
+				let status = env.call(func_id);
+				(status, env)
+			};
+			assert_eq!(call(101, &(1u32, 1u128)).0, Ok(RetCode::Success as u32));
+			assert_eq!(call(102, &(1u32, CONTRACT, 100u128)).0, Ok(RetCode::Success as u32));
+			assert_eq!(call(102, &(1u32, ALICE, 100u128)).0, Ok(RetCode::Success as u32));
+
+			// Transfers and approvals spend the contract's balance, not its caller's
+			assert_eq!(call(104, &(1u32, BOB, 10u128)).0, Ok(RetCode::Success as u32));
+			assert_eq!((Assets::balance(1, CONTRACT), Assets::balance(1, ALICE)), (90, 100));
+			assert_eq!(call(105, &(1u32, BOB, 20u128)).0, Ok(RetCode::Success as u32));
+			let (_, env) = call(109, &(1u32, CONTRACT, BOB));
+			assert_eq!(env.output_as::<u128>(), 20);
+			let (_, env) = call(109, &(1u32, ALICE, BOB));
+			assert_eq!(env.output_as::<u128>(), 0);
+
+			// The caller's assets only move through the approvals the caller gave the contract
+			assert!(call(106, &(1u32, ALICE, BOB, 10u128)).0.is_err());
+			assert_ok!(Assets::approve_transfer(Origin::signed(ALICE), 1, CONTRACT.into(), 30));
+			let (_, env) = call(109, &(1u32, ALICE, CONTRACT));
+			assert_eq!(env.output_as::<u128>(), 30);
+			assert_eq!(call(106, &(1u32, ALICE, BOB, 10u128)).0, Ok(RetCode::Success as u32));
+			assert_eq!((Assets::balance(1, ALICE), Assets::balance(1, BOB)), (90, 20));
+		});
+	}
+
+	#[test]
+	fn every_registered_function_is_implemented() {
+		new_test_ext(vec![]).execute_with(|| {
+			for function in functions::metadata::<Runtime>() {
+				let mut env = MockEnvironment::new(ALICE, CONTRACT);
+				assert_ne!(
+					env.call(function.id),
+					Err(DispatchError::Other("Unimplemented func_id")),
+					"{} is not implemented",
+					function.name,
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn insufficient_weight_traps_before_execution() {
+		new_test_ext(vec![]).execute_with(|| {
+			let weight_limit = weight_before_call(1, 4) - 1;
+			let mut env =
+				MockEnvironment::new(ALICE, CONTRACT).input(42u32).weight_limit(weight_limit);
+
+			assert_eq!(env.call(1), Err(pallet_contracts::Error::<Runtime>::OutOfGas.into()));
+			assert_eq!(TemplateModule::get_value(), 0);
+			assert_eq!(env.charged, 0);
+		});
+	}
+
+	#[test]
+	fn oversized_input_is_refused_uncharged() {
+		new_test_ext(vec![]).execute_with(|| {
+			let mut env = MockEnvironment::new(ALICE, CONTRACT);
+			env.input = vec![0; 513];
+
+			assert_eq!(env.call(3), Ok(RetCode::InputTooLarge as u32));
+			assert_eq!(env.charged, 0);
+		});
+	}
+
+	#[test]
+	fn deprecated_version_warns_until_retired() {
+		new_test_ext(vec![(ALICE, 10_000)]).execute_with(|| {
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input((1_000u32, BOB));
+
+			assert_eq!(env.call(2), Ok(RetCode::Success as u32));
+			assert!(!env.debug_buffer.is_empty());
+			assert_eq!(Balances::free_balance(&BOB), 1_000);
+
+			System::set_block_number(30 * DAYS);
+			assert_eq!(env.call(2), Ok(RetCode::Retired as u32));
+			assert_eq!(Balances::free_balance(&BOB), 1_000);
+		});
+	}
+
+	#[test]
+	fn governance_functions_are_only_served_to_permitted_contracts() {
+		new_test_ext(vec![(CONTRACT, 1_000 * UNIT)]).execute_with(|| {
+			let proposal = Call::System(frame_system::Call::remark { remark: vec![] }).encode();
+			let proposal_hash = BlakeTwo256::hash(&proposal);
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(proposal);
+
+			assert_eq!(env.call(801), Ok(RetCode::NotPermitted as u32));
+
+			assert_ok!(TemplateModule::grant_permission(
+				Origin::root(),
+				CONTRACT,
+				Permission::GovernanceContract
+			));
+			assert_eq!(env.call(801), Ok(RetCode::Success as u32));
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input((proposal_hash, 100 * UNIT));
+			assert_eq!(env.call(802), Ok(RetCode::Success as u32));
+			assert_eq!(Democracy::public_props().len(), 1);
+			assert!(Balances::reserved_balance(&CONTRACT) > 100 * UNIT);
+		});
+	}
+
+	#[test]
+	fn request_charges_fulfilment_and_call_back_and_returns_id() {
+		new_test_ext(vec![(CONTRACT, 1_000 * UNIT)]).execute_with(|| {
+			let gas_limit = MinRequestGasLimit::get();
+			let input = ([1u8; 4], gas_limit, vec![1u8; 8]);
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(input.clone());
+
+			assert_eq!(env.call(704), Ok(RetCode::Success as u32));
+			assert_eq!(env.output_as::<u64>(), 0);
+			let fulfilment = <Runtime as pallet_template::Config>::WeightInfo::fulfil_request(
+				MaxRequestResultLen::get(),
+			);
+			assert_eq!(
+				env.charged,
+				weight_before_call(704, input.encode().len()) + fulfilment + gas_limit
+			);
+			let request = TemplateModule::requests(0).unwrap();
+			assert_eq!((request.contract, request.gas_limit), (CONTRACT, gas_limit));
+			assert_eq!(Balances::reserved_balance(&CONTRACT), RequestDeposit::get());
+
+			let mut env =
+				MockEnvironment::new(ALICE, CONTRACT).input(([1u8; 4], gas_limit, vec![1u8; 257]));
+			assert_eq!(env.call(704), Ok(RetCode::InputTooLarge as u32));
+			let mut env =
+				MockEnvironment::new(ALICE, CONTRACT).input(([1u8; 4], gas_limit - 1, vec![1u8]));
+			assert!(env.call(704).is_err());
+		});
+	}
+
+	#[test]
+	fn oracle_value_writes_latest_aggregated_value() {
+		new_test_ext(vec![]).execute_with(|| {
+			let mut env = MockEnvironment::new(ALICE, CONTRACT).input(7u32);
+			assert_eq!(env.call(705), Ok(RetCode::Success as u32));
+			assert_eq!(env.output_as::<Option<TimestampedValue<u128>>>(), None);
+
+			Timestamp::set_timestamp(1_000);
+			assert_ok!(TemplateModule::feed(ALICE, 7, 10));
+			assert_ok!(TemplateModule::feed(BOB, 7, 30));
+			assert_eq!(env.call(705), Ok(RetCode::Success as u32));
+			assert_eq!(
+				env.output_as::<Option<TimestampedValue<u128>>>(),
+				Some(TimestampedValue { value: 30, timestamp: 1_000 })
+			);
+		});
+	}
+}
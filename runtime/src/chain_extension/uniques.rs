@@ -0,0 +1,116 @@
+//! Chain extension functions backed by `pallet_uniques`, allowing contracts to mint, transfer,
+//! burn and query runtime-level NFTs.
+//!
+//! Every mutating function is dispatched with the contract's own account as the signed origin, so
+//! the contract is the admin of the classes it creates and, unless told otherwise, the owner of
+//! the instances it mints.
+
+use super::env::ExtensionEnv;
+use codec::Encode;
+use frame_support::{traits::tokens::nonfungibles::Inspect, BoundedVec};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::SysConfig;
+use sp_runtime::{traits::StaticLookup, DispatchError};
+use sp_std::{convert::TryFrom, vec::Vec};
+
+type ClassId<T> = <T as pallet_uniques::Config>::ClassId;
+type InstanceId<T> = <T as pallet_uniques::Config>::InstanceId;
+type Uniques<T> = pallet_uniques::Pallet<T>;
+
+pub(super) fn call<T, E>(func_id: u32, env: &mut E) -> Result<(), DispatchError>
+where
+	T: SysConfig + pallet_contracts::Config + pallet_uniques::Config,
+	E: ExtensionEnv<T>,
+{
+	match func_id {
+		// do_nft_create_class
+		201 => {
+			let class: ClassId<T> = env.read_as()?;
+			let contract = env.address();
+
+			Uniques::<T>::create(
+				RawOrigin::Signed(contract.clone()).into(),
+				class,
+				T::Lookup::unlookup(contract),
+			)?;
+		},
+		// do_nft_mint
+		202 => {
+			let (class, instance, owner): (ClassId<T>, InstanceId<T>, Option<T::AccountId>) =
+				env.read_as()?;
+			let contract = env.address();
+			let owner = owner.unwrap_or_else(|| contract.clone());
+
+			Uniques::<T>::mint(
+				RawOrigin::Signed(contract).into(),
+				class,
+				instance,
+				T::Lookup::unlookup(owner),
+			)?;
+		},
+		// do_nft_burn
+		203 => {
+			let (class, instance): (ClassId<T>, InstanceId<T>) = env.read_as()?;
+			let contract = env.address();
+
+			Uniques::<T>::burn(RawOrigin::Signed(contract).into(), class, instance, None)?;
+		},
+		// do_nft_transfer
+		204 => {
+			let (class, instance, dest): (ClassId<T>, InstanceId<T>, T::AccountId) =
+				env.read_as()?;
+			let contract = env.address();
+
+			Uniques::<T>::transfer(
+				RawOrigin::Signed(contract).into(),
+				class,
+				instance,
+				T::Lookup::unlookup(dest),
+			)?;
+		},
+		// do_nft_set_attribute
+		205 => {
+			let (class, instance, key, value): (
+				ClassId<T>,
+				Option<InstanceId<T>>,
+				Vec<u8>,
+				Vec<u8>,
+			) = env.read_as()?;
+			let key = BoundedVec::try_from(key).map_err(|_| "NFT attribute key is too long.")?;
+			let value =
+				BoundedVec::try_from(value).map_err(|_| "NFT attribute value is too long.")?;
+			let contract = env.address();
+
+			Uniques::<T>::set_attribute(
+				RawOrigin::Signed(contract).into(),
+				class,
+				instance,
+				key,
+				value,
+			)?;
+		},
+		// do_nft_owner_of
+		206 => {
+			let (class, instance): (ClassId<T>, InstanceId<T>) = env.read_as()?;
+			let owner = <Uniques<T> as Inspect<_>>::owner(&class, &instance);
+
+			env.write(&owner.encode())
+				.map_err(|_| "Encountered an error when querying NFT ownership.")?;
+		},
+		// do_nft_attribute
+		207 => {
+			let (class, instance, key): (ClassId<T>, Option<InstanceId<T>>, Vec<u8>) =
+				env.read_as()?;
+			let attribute = match instance {
+				Some(instance) => <Uniques<T> as Inspect<_>>::attribute(&class, &instance, &key),
+				None => <Uniques<T> as Inspect<_>>::class_attribute(&class, &key),
+			};
+
+			env.write(&attribute.encode())
+				.map_err(|_| "Encountered an error when querying NFT attributes.")?;
+		},
+		_ => return Err(DispatchError::Other("Unimplemented func_id")),
+	}
+
+	Ok(())
+}
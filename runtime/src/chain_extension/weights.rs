@@ -0,0 +1,503 @@
+//! Weights of the chain extension functions.
+//!
+//! These are hand-written estimates, not benchmark results: `benchmarking.rs` benchmarks every
+//! function, but has not been run on reference hardware yet. Do not rely on them in production
+//! until this file is replaced by the benchmarks' output (see the README).
+//!
+//! Each function is benchmarked by calling it `r` times, or once with `n` bytes of input, from the
+//! fixture contract, so each weight function adds the weight of calling the contract to that of
+//! the calls it makes. `ExampleExtension` only charges the latter.
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, RuntimeDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for chain_extension.
+pub trait WeightInfo {
+	fn store_in_runtime(r: u32) -> Weight;
+	fn balance_transfer(r: u32) -> Weight;
+	fn get_balance(r: u32) -> Weight;
+	fn get_from_runtime(r: u32) -> Weight;
+	fn asset_create(r: u32) -> Weight;
+	fn asset_mint(r: u32) -> Weight;
+	fn asset_burn(r: u32) -> Weight;
+	fn asset_transfer(r: u32) -> Weight;
+	fn asset_approve(r: u32) -> Weight;
+	fn asset_transfer_approved(r: u32) -> Weight;
+	fn asset_balance_of(r: u32) -> Weight;
+	fn asset_total_supply(r: u32) -> Weight;
+	fn asset_allowance(r: u32) -> Weight;
+	fn nft_create_class(r: u32) -> Weight;
+	fn nft_mint(r: u32) -> Weight;
+	fn nft_burn(r: u32) -> Weight;
+	fn nft_transfer(r: u32) -> Weight;
+	fn nft_set_attribute(r: u32) -> Weight;
+	fn nft_owner_of(r: u32) -> Weight;
+	fn nft_attribute(r: u32) -> Weight;
+	fn dispatch_call(r: u32) -> Weight;
+	fn read_input_per_byte(n: u32) -> Weight;
+	fn random(r: u32) -> Weight;
+	fn random_per_byte(n: u32) -> Weight;
+	fn sr25519_verify(r: u32) -> Weight;
+	fn sr25519_verify_per_byte(n: u32) -> Weight;
+	fn ed25519_verify(r: u32) -> Weight;
+	fn ed25519_verify_per_byte(n: u32) -> Weight;
+	fn ecdsa_verify(r: u32) -> Weight;
+	fn ecdsa_verify_per_byte(n: u32) -> Weight;
+	fn ecdsa_recover(r: u32) -> Weight;
+	fn ecdsa_recover_per_byte(n: u32) -> Weight;
+	fn read_storage(r: u32) -> Weight;
+	fn read_storage_per_byte(n: u32) -> Weight;
+	fn emit_signal(r: u32) -> Weight;
+	fn emit_signal_per_byte(n: u32) -> Weight;
+	fn subscribe(r: u32) -> Weight;
+	fn unsubscribe(r: u32) -> Weight;
+	fn request(r: u32) -> Weight;
+	fn oracle_value(r: u32) -> Weight;
+}
+
+/// Execution weight of calling the fixture contract the functions are benchmarked from, which
+/// reads and writes `Contracts::ContractInfoOf`, and reads `Contracts::CodeStorage` and
+/// `Timestamp::Now`.
+const CONTRACT_CALL: Weight = 187_000_000;
+/// Execution weight of one `do_store_in_runtime` call, which writes
+/// `TemplateModule::ContractEntry`.
+const STORE_IN_RUNTIME: Weight = 20_000_000;
+/// Execution weight of one `do_balance_transfer` call, which reads and writes `System::Account`.
+const BALANCE_TRANSFER: Weight = 62_000_000;
+/// Execution weight of one `do_get_balance` call, besides reading its input.
+const GET_BALANCE: Weight = 15_000_000;
+/// Execution weight of one `do_get_from_runtime` call, which reads `TemplateModule::ContractEntry`.
+const GET_FROM_RUNTIME: Weight = 11_000_000;
+/// Execution weight of one `do_asset_create` call, which reads and writes `Assets::Asset`.
+const ASSET_CREATE: Weight = 42_000_000;
+/// Execution weight of one `do_asset_mint` call, which reads and writes `Assets::Asset` and
+/// `Assets::Account`.
+const ASSET_MINT: Weight = 54_000_000;
+/// Execution weight of one `do_asset_burn` call, which reads and writes `Assets::Asset` and
+/// `Assets::Account`.
+const ASSET_BURN: Weight = 60_000_000;
+/// Execution weight of one `do_asset_transfer` call, which reads and writes `Assets::Asset`,
+/// `Assets::Account` twice and `System::Account`.
+const ASSET_TRANSFER: Weight = 79_000_000;
+/// Execution weight of one `do_asset_approve` call, which reads and writes `Assets::Asset` and
+/// `Assets::Approvals`.
+const ASSET_APPROVE: Weight = 56_000_000;
+/// Execution weight of one `do_asset_transfer_approved` call, which reads and writes
+/// `Assets::Approvals`, `Assets::Asset`, `Assets::Account` twice and `System::Account`.
+const ASSET_TRANSFER_APPROVED: Weight = 104_000_000;
+/// Execution weight of one `do_asset_balance_of` call, which reads `Assets::Account`.
+const ASSET_BALANCE_OF: Weight = 16_000_000;
+/// Execution weight of one `do_asset_total_supply` call, which reads `Assets::Asset`.
+const ASSET_TOTAL_SUPPLY: Weight = 15_000_000;
+/// Execution weight of one `do_asset_allowance` call, which reads `Assets::Approvals`.
+const ASSET_ALLOWANCE: Weight = 16_000_000;
+/// Execution weight of one `do_nft_create_class` call, which reads and writes `Uniques::Class`, and
+/// writes `Uniques::ClassAccount`.
+const NFT_CREATE_CLASS: Weight = 41_000_000;
+/// Execution weight of one `do_nft_mint` call, which reads and writes `Uniques::Asset` and
+/// `Uniques::Class`, and writes `Uniques::Account`.
+const NFT_MINT: Weight = 53_000_000;
+/// Execution weight of one `do_nft_burn` call, which reads and writes `Uniques::Class` and
+/// `Uniques::Asset`, and writes `Uniques::Account` and `Uniques::InstanceMetadataOf`.
+const NFT_BURN: Weight = 59_000_000;
+/// Execution weight of one `do_nft_transfer` call, which reads and writes `Uniques::Asset`, reads
+/// `Uniques::Class`, and writes `Uniques::Account` twice.
+const NFT_TRANSFER: Weight = 44_000_000;
+/// Execution weight of one `do_nft_set_attribute` call, which reads and writes `Uniques::Class` and
+/// `Uniques::Attribute`, and reads `Uniques::InstanceMetadataOf`.
+const NFT_SET_ATTRIBUTE: Weight = 74_000_000;
+/// Execution weight of one `do_nft_owner_of` call, which reads `Uniques::Asset`.
+const NFT_OWNER_OF: Weight = 15_000_000;
+/// Execution weight of one `do_nft_attribute` call, which reads `Uniques::Attribute`.
+const NFT_ATTRIBUTE: Weight = 17_000_000;
+/// Execution weight of one `do_dispatch_call` call, besides reading and dispatching the call.
+const DISPATCH_CALL: Weight = 10_000_000;
+/// Execution weight of reading each byte of a function's input, unless the function has a larger
+/// per-byte weight of its own.
+const READ_INPUT_PER_BYTE: Weight = 3_000;
+/// Execution weight of one `do_random` call, which reads
+/// `RandomnessCollectiveFlip::RandomMaterial`.
+const RANDOM: Weight = 25_000_000;
+/// Execution weight of each byte of input of `do_random`.
+const RANDOM_PER_BYTE: Weight = 4_000;
+/// Execution weight of one `do_sr25519_verify` call, besides reading its input.
+const SR25519_VERIFY: Weight = 62_000_000;
+/// Execution weight of each byte of input of `do_sr25519_verify`.
+const SR25519_VERIFY_PER_BYTE: Weight = 3_000;
+/// Execution weight of one `do_ed25519_verify` call, besides reading its input.
+const ED25519_VERIFY: Weight = 59_000_000;
+/// Execution weight of each byte of input of `do_ed25519_verify`.
+const ED25519_VERIFY_PER_BYTE: Weight = 2_000;
+/// Execution weight of one `do_ecdsa_verify` call, besides reading its input.
+const ECDSA_VERIFY: Weight = 79_000_000;
+/// Execution weight of each byte of input of `do_ecdsa_verify`.
+const ECDSA_VERIFY_PER_BYTE: Weight = 2_000;
+/// Execution weight of one `do_ecdsa_recover` call, besides reading its input.
+const ECDSA_RECOVER: Weight = 77_000_000;
+/// Execution weight of each byte of input of `do_ecdsa_recover`.
+const ECDSA_RECOVER_PER_BYTE: Weight = 2_000;
+/// Execution weight of one `do_read_storage` call, besides reading the key.
+const READ_STORAGE: Weight = 21_000_000;
+/// Execution weight of each byte of input of `do_read_storage`.
+const READ_STORAGE_PER_BYTE: Weight = 1_000;
+/// Execution weight of one `do_emit_signal` call, besides reading its input.
+const EMIT_SIGNAL: Weight = 19_000_000;
+/// Execution weight of each byte of input of `do_emit_signal`.
+const EMIT_SIGNAL_PER_BYTE: Weight = 2_000;
+/// Execution weight of one `do_subscribe` call, which reads and writes
+/// `TemplateModule::Subscriptions` and `System::Account`, and reads `TemplateModule::CallDepth`.
+const SUBSCRIBE: Weight = 42_000_000;
+/// Execution weight of one `do_unsubscribe` call, which reads and writes
+/// `TemplateModule::Subscriptions` and `System::Account`, and reads `TemplateModule::CallDepth`.
+const UNSUBSCRIBE: Weight = 38_000_000;
+/// Execution weight of one `do_request` call, which reads and writes
+/// `TemplateModule::PendingRequests`, `TemplateModule::PendingRequestsOf`, `System::Account` and
+/// `TemplateModule::NextRequestId`, reads `TemplateModule::CallDepth` and `System::Number`, and
+/// writes `TemplateModule::Requests`.
+const REQUEST: Weight = 62_000_000;
+/// Execution weight of one `do_oracle_value` call, which reads `TemplateModule::OracleValues`.
+const ORACLE_VALUE: Weight = 22_000_000;
+
+/// Weight of calling the fixture contract to call a function `r` times, each call taking `call`
+/// and making `reads` and `writes`.
+fn repeated(db: RuntimeDbWeight, call: Weight, reads: Weight, writes: Weight, r: u32) -> Weight {
+	CONTRACT_CALL.saturating_add(db.reads_writes(3, 1)).saturating_add(
+		call.saturating_add(db.reads_writes(reads, writes)).saturating_mul(r.into()),
+	)
+}
+
+/// Weight of calling the fixture contract to call a function once with `n` bytes of input, the
+/// call taking `call` plus `per_byte` for each byte, and making `reads`.
+fn per_byte(db: RuntimeDbWeight, call: Weight, per_byte: Weight, reads: Weight, n: u32) -> Weight {
+	repeated(db, call, reads, 0, 1).saturating_add(per_byte.saturating_mul(n.into()))
+}
+
+/// Weights for chain_extension, from the estimates above and the runtime's database weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn store_in_runtime(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), STORE_IN_RUNTIME, 0, 1, r)
+	}
+
+	fn balance_transfer(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), BALANCE_TRANSFER, 1, 1, r)
+	}
+
+	fn get_balance(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), GET_BALANCE, 0, 0, r)
+	}
+
+	fn get_from_runtime(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), GET_FROM_RUNTIME, 1, 0, r)
+	}
+
+	fn asset_create(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_CREATE, 1, 1, r)
+	}
+
+	fn asset_mint(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_MINT, 2, 2, r)
+	}
+
+	fn asset_burn(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_BURN, 2, 2, r)
+	}
+
+	fn asset_transfer(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_TRANSFER, 4, 4, r)
+	}
+
+	fn asset_approve(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_APPROVE, 2, 2, r)
+	}
+
+	fn asset_transfer_approved(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_TRANSFER_APPROVED, 5, 5, r)
+	}
+
+	fn asset_balance_of(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_BALANCE_OF, 1, 0, r)
+	}
+
+	fn asset_total_supply(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_TOTAL_SUPPLY, 1, 0, r)
+	}
+
+	fn asset_allowance(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ASSET_ALLOWANCE, 1, 0, r)
+	}
+
+	fn nft_create_class(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_CREATE_CLASS, 1, 2, r)
+	}
+
+	fn nft_mint(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_MINT, 2, 3, r)
+	}
+
+	fn nft_burn(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_BURN, 2, 4, r)
+	}
+
+	fn nft_transfer(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_TRANSFER, 2, 3, r)
+	}
+
+	fn nft_set_attribute(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_SET_ATTRIBUTE, 3, 2, r)
+	}
+
+	fn nft_owner_of(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_OWNER_OF, 1, 0, r)
+	}
+
+	fn nft_attribute(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), NFT_ATTRIBUTE, 1, 0, r)
+	}
+
+	fn dispatch_call(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), DISPATCH_CALL, 0, 0, r)
+	}
+
+	fn read_input_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), 0, READ_INPUT_PER_BYTE, 0, n)
+	}
+
+	fn random(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), RANDOM, 1, 0, r)
+	}
+
+	fn random_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), RANDOM, RANDOM_PER_BYTE, 1, n)
+	}
+
+	fn sr25519_verify(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), SR25519_VERIFY, 0, 0, r)
+	}
+
+	fn sr25519_verify_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), SR25519_VERIFY, SR25519_VERIFY_PER_BYTE, 0, n)
+	}
+
+	fn ed25519_verify(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ED25519_VERIFY, 0, 0, r)
+	}
+
+	fn ed25519_verify_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), ED25519_VERIFY, ED25519_VERIFY_PER_BYTE, 0, n)
+	}
+
+	fn ecdsa_verify(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ECDSA_VERIFY, 0, 0, r)
+	}
+
+	fn ecdsa_verify_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), ECDSA_VERIFY, ECDSA_VERIFY_PER_BYTE, 0, n)
+	}
+
+	fn ecdsa_recover(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ECDSA_RECOVER, 0, 0, r)
+	}
+
+	fn ecdsa_recover_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), ECDSA_RECOVER, ECDSA_RECOVER_PER_BYTE, 0, n)
+	}
+
+	fn read_storage(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), READ_STORAGE, 1, 0, r)
+	}
+
+	fn read_storage_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), READ_STORAGE, READ_STORAGE_PER_BYTE, 1, n)
+	}
+
+	fn emit_signal(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), EMIT_SIGNAL, 0, 0, r)
+	}
+
+	fn emit_signal_per_byte(n: u32) -> Weight {
+		per_byte(T::DbWeight::get(), EMIT_SIGNAL, EMIT_SIGNAL_PER_BYTE, 0, n)
+	}
+
+	fn subscribe(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), SUBSCRIBE, 3, 2, r)
+	}
+
+	fn unsubscribe(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), UNSUBSCRIBE, 3, 2, r)
+	}
+
+	fn request(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), REQUEST, 6, 5, r)
+	}
+
+	fn oracle_value(r: u32) -> Weight {
+		repeated(T::DbWeight::get(), ORACLE_VALUE, 1, 0, r)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn store_in_runtime(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), STORE_IN_RUNTIME, 0, 1, r)
+	}
+
+	fn balance_transfer(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), BALANCE_TRANSFER, 1, 1, r)
+	}
+
+	fn get_balance(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), GET_BALANCE, 0, 0, r)
+	}
+
+	fn get_from_runtime(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), GET_FROM_RUNTIME, 1, 0, r)
+	}
+
+	fn asset_create(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_CREATE, 1, 1, r)
+	}
+
+	fn asset_mint(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_MINT, 2, 2, r)
+	}
+
+	fn asset_burn(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_BURN, 2, 2, r)
+	}
+
+	fn asset_transfer(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_TRANSFER, 4, 4, r)
+	}
+
+	fn asset_approve(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_APPROVE, 2, 2, r)
+	}
+
+	fn asset_transfer_approved(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_TRANSFER_APPROVED, 5, 5, r)
+	}
+
+	fn asset_balance_of(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_BALANCE_OF, 1, 0, r)
+	}
+
+	fn asset_total_supply(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_TOTAL_SUPPLY, 1, 0, r)
+	}
+
+	fn asset_allowance(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ASSET_ALLOWANCE, 1, 0, r)
+	}
+
+	fn nft_create_class(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_CREATE_CLASS, 1, 2, r)
+	}
+
+	fn nft_mint(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_MINT, 2, 3, r)
+	}
+
+	fn nft_burn(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_BURN, 2, 4, r)
+	}
+
+	fn nft_transfer(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_TRANSFER, 2, 3, r)
+	}
+
+	fn nft_set_attribute(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_SET_ATTRIBUTE, 3, 2, r)
+	}
+
+	fn nft_owner_of(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_OWNER_OF, 1, 0, r)
+	}
+
+	fn nft_attribute(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), NFT_ATTRIBUTE, 1, 0, r)
+	}
+
+	fn dispatch_call(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), DISPATCH_CALL, 0, 0, r)
+	}
+
+	fn read_input_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), 0, READ_INPUT_PER_BYTE, 0, n)
+	}
+
+	fn random(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), RANDOM, 1, 0, r)
+	}
+
+	fn random_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), RANDOM, RANDOM_PER_BYTE, 1, n)
+	}
+
+	fn sr25519_verify(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), SR25519_VERIFY, 0, 0, r)
+	}
+
+	fn sr25519_verify_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), SR25519_VERIFY, SR25519_VERIFY_PER_BYTE, 0, n)
+	}
+
+	fn ed25519_verify(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ED25519_VERIFY, 0, 0, r)
+	}
+
+	fn ed25519_verify_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), ED25519_VERIFY, ED25519_VERIFY_PER_BYTE, 0, n)
+	}
+
+	fn ecdsa_verify(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ECDSA_VERIFY, 0, 0, r)
+	}
+
+	fn ecdsa_verify_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), ECDSA_VERIFY, ECDSA_VERIFY_PER_BYTE, 0, n)
+	}
+
+	fn ecdsa_recover(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ECDSA_RECOVER, 0, 0, r)
+	}
+
+	fn ecdsa_recover_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), ECDSA_RECOVER, ECDSA_RECOVER_PER_BYTE, 0, n)
+	}
+
+	fn read_storage(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), READ_STORAGE, 1, 0, r)
+	}
+
+	fn read_storage_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), READ_STORAGE, READ_STORAGE_PER_BYTE, 1, n)
+	}
+
+	fn emit_signal(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), EMIT_SIGNAL, 0, 0, r)
+	}
+
+	fn emit_signal_per_byte(n: u32) -> Weight {
+		per_byte(RocksDbWeight::get(), EMIT_SIGNAL, EMIT_SIGNAL_PER_BYTE, 0, n)
+	}
+
+	fn subscribe(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), SUBSCRIBE, 3, 2, r)
+	}
+
+	fn unsubscribe(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), UNSUBSCRIBE, 3, 2, r)
+	}
+
+	fn request(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), REQUEST, 6, 5, r)
+	}
+
+	fn oracle_value(r: u32) -> Weight {
+		repeated(RocksDbWeight::get(), ORACLE_VALUE, 1, 0, r)
+	}
+}
@@ -6,21 +6,30 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
-use frame_system::limits::{BlockLength, BlockWeights};
+use frame_system::{
+	limits::{BlockLength, BlockWeights},
+	EnsureRoot,
+};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3, _4},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 
+use codec::Decode;
 use pallet_contracts::weights::WeightInfo;
+use scale_info::TypeInfo;
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -33,7 +42,8 @@ pub use frame_support::{
 	dispatch::DispatchErrorWithPostInfo,
 	log::{error, info},
 	parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	storage::storage_prefix,
+	traits::{Contains, KeyOwnerProofSystem, PalletInfoAccess, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		DispatchClass, DispatchInfo, IdentityFee, PostDispatchInfo, Weight,
@@ -49,6 +59,7 @@ pub use sp_runtime::{DispatchError, Perbill, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
+use pallet_template::{HasPermission, MatchEvent, Permission, ReentrancyPolicy};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -69,7 +80,7 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
-mod chain_extension;
+pub mod chain_extension;
 use chain_extension::ExampleExtension;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
@@ -108,10 +119,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -135,6 +146,11 @@ pub const DAYS: BlockNumber = HOURS * 24;
 /// This is used to limit the maximal weight of a single extrinsic.
 const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
 
+/// Share of the block weight `pallet_template` may use to deliver events to subscribed contracts
+/// in `on_initialize`, kept well below `AVERAGE_ON_INITIALIZE_RATIO`, which contract deletion may
+/// use up on its own.
+const DELIVERY_RATIO: Perbill = Perbill::from_percent(2);
+
 /// We allow for 2 seconds of compute with a 6 second average block time.
 const MAXIMUM_BLOCK_WEIGHT: Weight = 2 * WEIGHT_PER_SECOND;
 
@@ -306,6 +322,24 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
 
+/// Whitelist of runtime calls contracts may dispatch through the chain extension's generic
+/// dispatch functions (func_ids 301 and 302).
+pub struct ContractCallFilter;
+
+impl Contains<Call> for ContractCallFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Balances(BalancesCall::transfer { .. }) |
+				Call::Balances(BalancesCall::transfer_keep_alive { .. }) |
+				Call::TemplateModule(pallet_template::Call::insert_number { .. }) |
+				Call::Assets(pallet_assets::Call::transfer { .. }) |
+				Call::Assets(pallet_assets::Call::transfer_keep_alive { .. }) |
+				Call::Uniques(pallet_uniques::Call::transfer { .. })
+		)
+	}
+}
+
 parameter_types! {
 	pub ContractDeposit: Balance = deposit(
 		1,
@@ -362,11 +396,332 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+/// Enacts the referenda passed by `pallet_democracy`.
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const InstantAllowed: bool = true;
+	pub const MinimumDeposit: Balance = 100 * UNIT;
+	pub const EnactmentPeriod: BlockNumber = DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const PreimageByteDeposit: Balance = 10 * MILLIUNIT;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+/// Public proposals and referenda, which permissioned contracts may also take part in through the
+/// chain extension's governance functions. The council is the external origin.
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	type CancelProposalOrigin = EnsureRoot<AccountId>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = ();
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * UNIT;
+	pub const ApprovalDeposit: Balance = UNIT;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * UNIT;
+	pub const MetadataDepositPerByte: Balance = UNIT;
+}
+
+/// Runtime-level fungible assets, exposed to contracts through the chain extension.
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const ClassDeposit: Balance = 100 * UNIT;
+	pub const InstanceDeposit: Balance = UNIT;
+	pub const UniquesMetadataDepositBase: Balance = 10 * UNIT;
+	pub const AttributeDepositBase: Balance = 10 * UNIT;
+	pub const UniquesDepositPerByte: Balance = UNIT;
+	pub const UniquesStringLimit: u32 = 128;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 256;
+}
+
+/// Runtime-level NFTs, exposed to contracts through the chain extension.
+impl pallet_uniques::Config for Runtime {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = UniquesDepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// Storage contracts may read through the chain extension's `do_read_storage`. Whole maps are
+	// exposed by listing their prefix.
+	pub ReadableStoragePrefixes: Vec<Vec<u8>> = vec![
+		storage_prefix(System::name().as_bytes(), b"Number").to_vec(),
+		storage_prefix(Timestamp::name().as_bytes(), b"Now").to_vec(),
+		storage_prefix(TemplateModule::name().as_bytes(), b"ContractEntry").to_vec(),
+	];
+}
+
+parameter_types! {
+	// Contracts allowed to take part in `Democracy` through the chain extension's governance
+	// functions, granted by `TemplateModule::grant_permission`.
+	pub const GovernanceContract: Permission = Permission::GovernanceContract;
+}
+
+impl chain_extension::Config for Runtime {
+	type ReadableStoragePrefixes = ReadableStoragePrefixes;
+	type CallFilter = ContractCallFilter;
+	type GovernanceContracts = HasPermission<Runtime, GovernanceContract>;
+	type WeightInfo = chain_extension::weights::SubstrateWeight<Runtime>;
+
+	fn max_input_len(func_id: u32) -> u32 {
+		match func_id {
+			// The dispatch and signature functions take arbitrarily long calls and messages
+			301 | 302 | 501..=504 => chain_extension::MAX_INPUT_LEN,
+			// democracy_note_preimage takes an arbitrarily long proposal
+			801 => chain_extension::MAX_INPUT_LEN,
+			// do_emit_signal takes a topic and a length-prefixed payload
+			701 => 32 + 4 + MaxSignalPayloadLen::get(),
+			// do_request takes a selector, a gas limit and a length-prefixed payload
+			704 => 4 + 8 + 4 + MaxRequestPayloadLen::get(),
+			// Everything else takes a few ids, accounts and balances, or a short key and value
+			_ => 512,
+		}
+	}
+
+	fn retired_from(func_id: u32) -> Option<BlockNumber> {
+		match func_id {
+			// do_balance_transfer taking a `u32` amount, superseded by version 1
+			2 => Some(30 * DAYS),
+			_ => None,
+		}
+	}
+}
+
+/// Runtime events contracts may subscribe to through the chain extension's `do_subscribe`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
+pub enum EventFilter {
+	/// Transfers of the native currency to the account.
+	TransferTo(AccountId),
+	/// Changes of the value stored in `TemplateModule`'s `ContractEntry`.
+	ContractEntryChanged,
+}
+
+/// Builds the `EventFilter` the benchmarks of `pallet_template` and the chain extension subscribe
+/// with.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TemplateBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_template::BenchmarkHelper<EventFilter> for TemplateBenchmarkHelper {
+	fn event_filter() -> EventFilter {
+		EventFilter::ContractEntryChanged
+	}
+}
+
+impl MatchEvent<Event> for EventFilter {
+	fn matches(&self, event: &Event) -> bool {
+		match (self, event) {
+			(
+				EventFilter::TransferTo(account),
+				Event::Balances(pallet_balances::Event::Transfer(_, to, _)),
+			) => to == account,
+			(
+				EventFilter::ContractEntryChanged,
+				Event::TemplateModule(pallet_template::Event::CalledPalletFromContract(_)),
+			) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	pub const MaxSignalPayloadLen: u32 = 256;
+	// Contracts called by `call_smart_contract` may store a number through the chain extension,
+	// and call contracts through the pallet again a couple of times at most
+	pub const MaxCallDepth: u32 = 3;
+	pub const Reentrancy: ReentrancyPolicy = ReentrancyPolicy::Allow;
+	pub const MaxSubscriptions: u32 = 100;
+	pub const MaxSubscriptionsPerContract: u32 = 4;
+	pub const SubscriptionDeposit: Balance = deposit(1, 64);
+	pub const MaxMatchedEvents: u32 = 1_000;
+	pub const MaxQueuedDeliveries: u32 = 100;
+	// About four deliveries per block at most
+	pub DeliveryWeight: Weight = DELIVERY_RATIO * RuntimeBlockWeights::get().max_block;
+	pub const DeliveryGasLimit: Weight = 10 * WEIGHT_PER_SECOND / 1_000;
+	pub const MaxRequestPayloadLen: u32 = 256;
+	pub const MaxRequestResultLen: u32 = 1024;
+	pub const MaxPendingRequests: u32 = 100;
+	pub const MaxRequestsPerContract: u32 = 4;
+	pub const MinRequestGasLimit: Weight = WEIGHT_PER_SECOND / 1_000;
+	// A request holds an account, a selector, a gas limit, a block number and its payload
+	pub const RequestDeposit: Balance = deposit(1, 48 + 256);
+	pub const RequestTimeout: BlockNumber = DAYS;
+	pub const MaxEvaluations: u32 = 16;
+	pub const MaxEvaluationInputLen: u32 = 256;
+	pub const MaxEvaluationOutputLen: u32 = 1024;
+	// Evaluations aren't paid for, but still limited to a tenth of a second each
+	pub const EvaluationGasLimit: Weight = WEIGHT_PER_SECOND / 10;
+	pub const EvaluationPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const MaxFeedersPerKey: u32 = 16;
+	// Values fed more than ten minutes ago are no longer aggregated
+	pub const OracleMaxAge: u64 = 10 * 60_000;
+	pub const SponsorshipPeriod: BlockNumber = DAYS;
+	// A sponsorship is keyed by a contract and a selector, and holds a sponsor and a quota
+	pub const SponsorshipDeposit: Balance = deposit(1, 72);
+}
+
+/// Evaluates contracts as the runtime API does dry-runs, refusing chain extension functions that
+/// change state.
+pub struct ReadOnlyQuery;
+
+impl pallet_template::ExecuteQuery for ReadOnlyQuery {
+	fn execute_query<R>(f: impl FnOnce() -> R) -> R {
+		chain_extension::read_only(f)
+	}
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+	type MaxSignalPayloadLen = MaxSignalPayloadLen;
+	type MaxCallDepth = MaxCallDepth;
+	type Reentrancy = Reentrancy;
+	type EventFilter = EventFilter;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TemplateBenchmarkHelper;
+	type MaxSubscriptions = MaxSubscriptions;
+	type MaxSubscriptionsPerContract = MaxSubscriptionsPerContract;
+	type SubscriptionDeposit = SubscriptionDeposit;
+	type MaxMatchedEvents = MaxMatchedEvents;
+	type MaxQueuedDeliveries = MaxQueuedDeliveries;
+	type DeliveryWeight = DeliveryWeight;
+	type DeliveryGasLimit = DeliveryGasLimit;
+	type DeliveryWeightPrice = TransactionPayment;
+	type FulfilOrigin = EnsureRoot<AccountId>;
+	type MaxRequestPayloadLen = MaxRequestPayloadLen;
+	type MaxRequestResultLen = MaxRequestResultLen;
+	type MaxPendingRequests = MaxPendingRequests;
+	type MaxRequestsPerContract = MaxRequestsPerContract;
+	type MinRequestGasLimit = MinRequestGasLimit;
+	type RequestDeposit = RequestDeposit;
+	type RequestTimeout = RequestTimeout;
+	type AuthorityId = pallet_template::crypto::EvaluatorId;
+	type EvaluationOrigin = EnsureRoot<AccountId>;
+	type Query = ReadOnlyQuery;
+	type MaxEvaluations = MaxEvaluations;
+	type MaxEvaluationInputLen = MaxEvaluationInputLen;
+	type MaxEvaluationOutputLen = MaxEvaluationOutputLen;
+	type EvaluationGasLimit = EvaluationGasLimit;
+	type EvaluationPriority = EvaluationPriority;
+	// Keys are ids agreed on by feeders and contracts, such as of the asset whose price is fed
+	type OracleKey = u32;
+	type OracleValue = u128;
+	type OracleTime = Timestamp;
+	type MaxFeedersPerKey = MaxFeedersPerKey;
+	type OracleMaxAge = OracleMaxAge;
+	type SponsorshipPeriod = SponsorshipPeriod;
+	type SponsorshipDeposit = SponsorshipDeposit;
+	type PermissionOrigin = EnsureRoot<AccountId>;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -385,8 +740,13 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the pallet-template in the runtime.
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Storage, Config<T>, Event<T>, Origin<T>, ValidateUnsigned},
 		Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -404,7 +764,8 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	// Charges the fees of sponsored `call_smart_contract` calls to their sponsor
+	pallet_template::ChargeSponsoredTransactionPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -557,6 +918,7 @@ impl_runtime_apis! {
 			use frame_benchmarking::{list_benchmark, Benchmarking, BenchmarkList};
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
+			use chain_extension::benchmarking::Pallet as ChainExtensionBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 
@@ -564,6 +926,7 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, chain_extension, ChainExtensionBench::<Runtime>);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -578,6 +941,9 @@ impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			impl frame_system_benchmarking::Config for Runtime {}
 
+			use chain_extension::benchmarking::Pallet as ChainExtensionBench;
+			impl chain_extension::benchmarking::Config for Runtime {}
+
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
@@ -598,6 +964,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, chain_extension, ChainExtensionBench::<Runtime>);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -636,4 +1003,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl chain_extension::ChainExtensionApi<Block> for Runtime {
+		fn metadata() -> Vec<u8> {
+			chain_extension::extension_metadata::<Runtime>().encode()
+		}
+	}
+
+	impl chain_extension::ContractsQueryApi<Block, AccountId, Balance, Hash> for Runtime {
+		fn query_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			input_data: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractExecResult {
+			chain_extension::read_only(|| {
+				Contracts::bare_call(origin, dest, value, gas_limit, input_data, CONTRACTS_DEBUG_OUTPUT)
+			})
+		}
+
+		fn query_instantiate(
+			origin: AccountId,
+			endowment: Balance,
+			gas_limit: u64,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId>
+		{
+			chain_extension::read_only(|| {
+				Contracts::bare_instantiate(origin, endowment, gas_limit, code, data, salt, CONTRACTS_DEBUG_OUTPUT)
+			})
+		}
+	}
+
 }
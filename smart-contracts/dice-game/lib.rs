@@ -0,0 +1,146 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract(env = extension::CustomEnvironment)]
+/// A dice game settled with runtime randomness obtained through the chain extension.
+///
+/// Players bet on the outcome of a six-sided die roll and are paid six times their stake if they
+/// guess right. The roll is not made when the bet is placed: `do_random` returns randomness that
+/// has been knowable since its `determined_at` block and changes with every block. A bet placed
+/// at block `n` is therefore rolled with the randomness determined at block `n + 1`, which is
+/// unknown when the bet is placed. It can only be settled while `do_random` returns that
+/// randomness, so the player can not pick a roll by picking when to settle. A bet not settled in
+/// time expires and is lost.
+mod dice_game {
+	use extension::ContractError;
+	use ink_prelude::vec::Vec;
+	use ink_storage::collections::HashMap as StorageHashMap;
+	use scale::Encode;
+
+	/// Multiplier applied to the stake of a winning bet.
+	const PAYOUT_MULTIPLIER: Balance = 6;
+	/// Blocks between the block a bet is placed at and the block its randomness is determined at.
+	const ROLL_DELAY: BlockNumber = 1;
+
+	#[ink(storage)]
+	pub struct DiceGame {
+		/// Open bets as `(guess, placed_at, stake)`, one per player.
+		bets: StorageHashMap<AccountId, (u8, BlockNumber, Balance)>,
+	}
+
+	#[ink(event)]
+	pub struct BetPlaced {
+		#[ink(topic)]
+		player: AccountId,
+		guess: u8,
+		stake: Balance,
+	}
+
+	#[ink(event)]
+	pub struct BetSettled {
+		#[ink(topic)]
+		player: AccountId,
+		roll: u8,
+		won: bool,
+	}
+
+	#[ink(event)]
+	pub struct BetExpired {
+		#[ink(topic)]
+		player: AccountId,
+	}
+
+	#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+	pub enum GameError {
+		/// The guess is not a face of a six-sided die
+		InvalidGuess,
+		/// The bet was placed without transferring a stake
+		NoStake,
+		/// The player already has an open bet
+		BetAlreadyPlaced,
+		/// The player has no open bet
+		NoBet,
+		/// The randomness of the bet is not determined yet
+		TooEarly,
+		/// The contract could not pay out the winnings
+		PayoutFailed,
+		/// The chain extension returned an error
+		Extension(ContractError),
+	}
+
+	impl From<ContractError> for GameError {
+		fn from(e: ContractError) -> Self {
+			GameError::Extension(e)
+		}
+	}
+
+	impl DiceGame {
+		/// Create the game. Any value transferred funds the payouts.
+		#[ink(constructor, payable)]
+		pub fn new() -> Self {
+			Self { bets: Default::default() }
+		}
+
+		/// Bet the transferred value on the die landing on `guess`
+		#[ink(message, payable)]
+		pub fn place_bet(&mut self, guess: u8) -> Result<(), GameError> {
+			if !(1..=6).contains(&guess) {
+				return Err(GameError::InvalidGuess)
+			}
+			let stake = self.env().transferred_balance();
+			if stake == 0 {
+				return Err(GameError::NoStake)
+			}
+			let player = self.env().caller();
+			if self.bets.contains_key(&player) {
+				return Err(GameError::BetAlreadyPlaced)
+			}
+
+			self.bets.insert(player, (guess, self.env().block_number(), stake));
+			self.env().emit_event(BetPlaced { player, guess, stake });
+			Ok(())
+		}
+
+		/// Roll the die for the caller's open bet, paying out if the guess was right. Returns
+		/// whether the bet was won, which it is not once it has expired.
+		#[ink(message)]
+		pub fn settle(&mut self) -> Result<bool, GameError> {
+			let player = self.env().caller();
+			let (guess, placed_at, stake) = *self.bets.get(&player).ok_or(GameError::NoBet)?;
+
+			// Bind the randomness to this particular bet
+			let mut subject: Vec<u8> = player.encode();
+			subject.extend(placed_at.encode());
+			let random = self.env().extension().do_random(subject)?;
+			let rolled_at = placed_at.saturating_add(ROLL_DELAY);
+			if random.determined_at < rolled_at {
+				return Err(GameError::TooEarly)
+			}
+			if random.determined_at > rolled_at {
+				self.bets.take(&player);
+				self.env().emit_event(BetExpired { player });
+				return Ok(false)
+			}
+
+			let roll = random.seed.as_ref()[0] % 6 + 1;
+			let won = roll == guess;
+			if won {
+				self.env()
+					.transfer(player, stake.saturating_mul(PAYOUT_MULTIPLIER))
+					.map_err(|_| GameError::PayoutFailed)?;
+			}
+			self.bets.take(&player);
+
+			self.env().emit_event(BetSettled { player, roll, won });
+			Ok(won)
+		}
+
+		/// The open bet of `player` as `(guess, placed_at, stake)`, if any
+		#[ink(message)]
+		pub fn bet_of(&self, player: AccountId) -> Option<(u8, BlockNumber, Balance)> {
+			self.bets.get(&player).copied()
+		}
+	}
+}
@@ -1,57 +1,41 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use ink_env::{AccountId, Environment};
+use ink_env::Environment;
 use ink_lang as ink;
 
-#[ink::chain_extension]
-pub trait ChainExtension {
-	type ErrorCode = ContractError;
-	// Use the #[ink(extension = {func_id})] syntax to specify the function id.
-	// We will `match` on this in the runtime to map this to some custom pallet extrinsic
-	#[ink(extension = 1)]
-	/// Calls the runtime chain extension with func_id 1, defined in the runtime, which receives a
-	/// number and stores it in a runtime storage value
-	fn do_store_in_runtime(key: u32) -> Result<u32, ContractError>;
-	#[ink(extension = 2)]
-	/// Calls the runtime chain extension with func_id 2, which uses pallet_balances::transfer to
-	/// perform a transfer of `value` from the sender, to `recipient`
-	fn do_balance_transfer(value: u32, recipient: AccountId) -> Result<u32, ContractError>;
-
-	#[ink(extension = 3)]
-	/// Calls the runtime chain extension with func_id 3, which calls free_balance of
-	/// pallet_balances for the given account.
-	fn do_get_balance(account: AccountId) -> Result<u32, ContractError>;
-
-	#[ink(extension = 4, returns_result = false)]
-	/// Calls the runtime chain extension with func_id 4, to get the current value held in the
-	/// runtime storage value.
-	fn do_get_from_runtime() -> u32;
-}
+/// Balance type shared by the native currency and runtime assets.
+pub type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+/// Identifier of an asset managed by the runtime's `pallet_assets`.
+pub type AssetId = u32;
+/// Identifier of an NFT class managed by the runtime's `pallet_uniques`.
+pub type ClassId = u32;
+/// Identifier of an NFT within its class.
+pub type InstanceId = u32;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-pub enum ContractError {
-	FailToCallRuntime,
-	UnknownStatusCode,
-	InvalidScaleEncoding,
-}
+// The chain extension definitions are generated from the runtime, see `../extension-bindings`
+pub use extension_bindings::{ChainExtension, ContractError, RandomOutput};
 
-impl From<scale::Error> for ContractError {
-	fn from(_: scale::Error) -> Self {
-		ContractError::InvalidScaleEncoding
-	}
-}
-
-// Define error codes here for error situations not sufficiently captured in `Result`s returned from
-// runtime. Then, return error code from runtime chain extension using Ok(RetVal::Converging({your
-// error code}))
-impl ink_env::chain_extension::FromStatusCode for ContractError {
-	fn from_status_code(status_code: u32) -> Result<(), Self> {
-		match status_code {
-			0 => Ok(()),
-			_ => Err(Self::UnknownStatusCode),
-		}
-	}
+/// Runtime storage keys readable with `do_read_storage`, i.e.
+/// `twox128(pallet name) ++ twox128(storage item name)`.
+pub mod storage_keys {
+	/// `System::Number`, the current block number.
+	pub const SYSTEM_NUMBER: [u8; 32] = [
+		0x26, 0xaa, 0x39, 0x4e, 0xea, 0x56, 0x30, 0xe0, 0x7c, 0x48, 0xae, 0x0c, 0x95, 0x58, 0xce,
+		0xf7, 0x02, 0xa5, 0xc1, 0xb1, 0x9a, 0xb7, 0xa0, 0x4f, 0x53, 0x6c, 0x51, 0x9a, 0xca, 0x49,
+		0x83, 0xac,
+	];
+	/// `Timestamp::Now`, the timestamp of the current block.
+	pub const TIMESTAMP_NOW: [u8; 32] = [
+		0xf0, 0xc3, 0x65, 0xc3, 0xcf, 0x59, 0xd6, 0x71, 0xeb, 0x72, 0xda, 0x0e, 0x7a, 0x41, 0x13,
+		0xc4, 0x9f, 0x1f, 0x05, 0x15, 0xf4, 0x62, 0xcd, 0xcf, 0x84, 0xe0, 0xf1, 0xd6, 0x04, 0x5d,
+		0xfc, 0xbb,
+	];
+	/// `TemplateModule::ContractEntry`, the value stored by `do_store_in_runtime`.
+	pub const TEMPLATE_MODULE_CONTRACT_ENTRY: [u8; 32] = [
+		0x17, 0x7e, 0x68, 0x57, 0xfb, 0x1d, 0x0e, 0x40, 0x93, 0x76, 0x12, 0x2f, 0xee, 0x3a, 0xd4,
+		0xf8, 0x3d, 0xb9, 0x1e, 0x3d, 0x1c, 0x67, 0x97, 0xa3, 0xaf, 0x10, 0x27, 0x48, 0x5f, 0x78,
+		0x88, 0xb1,
+	];
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -72,12 +56,24 @@ impl Environment for CustomEnvironment {
 #[ink::contract(env = crate::CustomEnvironment)]
 /// A smart contract with a custom environment, necessary for the chain extension
 mod contract_with_extension {
-	use super::ContractError;
+	use super::{storage_keys, AssetId, ClassId, ContractError, InstanceId, RandomOutput};
+	use ink_prelude::vec::Vec;
+	use scale::{Decode, Encode};
+
+	/// Selector of `on_transfer`, which the contract subscribes to transfers to it with.
+	const ON_TRANSFER_SELECTOR: [u8; 4] = [0x0e, 0x7e, 0x00, 0x01];
+	/// Selector of `on_number_fulfilled`, which the contract requests numbers with.
+	const ON_NUMBER_FULFILLED_SELECTOR: [u8; 4] = [0x0e, 0x7e, 0x00, 0x02];
+	/// Gas limit of the call back of a request, enough to store a number.
+	const REQUEST_GAS_LIMIT: u64 = 10_000_000_000;
 
 	/// Defines the storage of our contract.
 	#[ink(storage)]
 	pub struct RuntimeInterface {
 		stored_number: u32,
+		/// Account that instantiated the contract, the only one allowed to spend what the
+		/// contract holds.
+		owner: AccountId,
 	}
 
 	#[ink(event)]
@@ -90,7 +86,16 @@ mod contract_with_extension {
 	impl RuntimeInterface {
 		#[ink(constructor)]
 		pub fn default() -> Self {
-			Self { stored_number: Default::default() }
+			Self { stored_number: Default::default(), owner: Self::env().caller() }
+		}
+
+		/// Fails with `ContractError::NotPermitted` unless the caller is the owner.
+		fn ensure_owner(&self) -> Result<(), ContractError> {
+			if self.env().caller() == self.owner {
+				Ok(())
+			} else {
+				Err(ContractError::NotPermitted)
+			}
 		}
 
 		/// Get currently stored value
@@ -124,7 +129,7 @@ mod contract_with_extension {
 		#[ink(message)]
 		pub fn extended_transfer(
 			&mut self,
-			amount: u32,
+			amount: Balance,
 			recipient: AccountId,
 		) -> Result<(), ContractError> {
 			self.env().extension().do_balance_transfer(amount, recipient)?;
@@ -133,10 +138,8 @@ mod contract_with_extension {
 
 		#[ink(message)]
 		/// Get the free balance for the given account. Included mainly for testing
-		pub fn get_balance(&mut self, account: AccountId) -> Result<u32, ContractError> {
-			let value = self.env().extension().do_get_balance(account);
-			self.env().emit_event(ResultNum { number: value? });
-			value
+		pub fn get_balance(&mut self, account: AccountId) -> Result<Balance, ContractError> {
+			self.env().extension().do_get_balance(account)
 		}
 
 		#[ink(message)]
@@ -146,5 +149,532 @@ mod contract_with_extension {
 			self.env().emit_event(ResultNum { number: value? });
 			value
 		}
+
+		/// Create a runtime asset administered by this contract
+		#[ink(message)]
+		pub fn create_asset(
+			&mut self,
+			asset_id: AssetId,
+			min_balance: Balance,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_asset_create(asset_id, min_balance)
+		}
+
+		/// Mint an asset administered by this contract to `beneficiary`
+		#[ink(message)]
+		pub fn mint_asset(
+			&mut self,
+			asset_id: AssetId,
+			beneficiary: AccountId,
+			amount: Balance,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_asset_mint(asset_id, beneficiary, amount)
+		}
+
+		/// Burn an asset administered by this contract from `who`
+		#[ink(message)]
+		pub fn burn_asset(
+			&mut self,
+			asset_id: AssetId,
+			who: AccountId,
+			amount: Balance,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_asset_burn(asset_id, who, amount)
+		}
+
+		/// PSP22-style transfer out of this contract's runtime asset balance
+		#[ink(message)]
+		pub fn transfer_asset(
+			&mut self,
+			asset_id: AssetId,
+			to: AccountId,
+			value: Balance,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_asset_transfer(asset_id, to, value)
+		}
+
+		/// PSP22-style approval of `spender` over this contract's runtime asset balance
+		#[ink(message)]
+		pub fn approve_asset(
+			&mut self,
+			asset_id: AssetId,
+			spender: AccountId,
+			value: Balance,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_asset_approve(asset_id, spender, value)
+		}
+
+		/// PSP22-style `transfer_from`, spending an approval given to this contract
+		#[ink(message)]
+		pub fn transfer_asset_from(
+			&mut self,
+			asset_id: AssetId,
+			from: AccountId,
+			to: AccountId,
+			value: Balance,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_asset_transfer_approved(asset_id, from, to, value)
+		}
+
+		/// Runtime asset balance of `owner`
+		#[ink(message)]
+		pub fn asset_balance_of(
+			&self,
+			asset_id: AssetId,
+			owner: AccountId,
+		) -> Result<Balance, ContractError> {
+			self.env().extension().do_asset_balance_of(asset_id, owner)
+		}
+
+		/// Total issuance of a runtime asset
+		#[ink(message)]
+		pub fn asset_total_supply(&self, asset_id: AssetId) -> Result<Balance, ContractError> {
+			self.env().extension().do_asset_total_supply(asset_id)
+		}
+
+		/// Remaining amount `spender` may transfer out of `owner`'s runtime asset balance
+		#[ink(message)]
+		pub fn asset_allowance(
+			&self,
+			asset_id: AssetId,
+			owner: AccountId,
+			spender: AccountId,
+		) -> Result<Balance, ContractError> {
+			self.env().extension().do_asset_allowance(asset_id, owner, spender)
+		}
+
+		/// Create an NFT class administered by this contract
+		#[ink(message)]
+		pub fn create_nft_class(&mut self, class: ClassId) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_nft_create_class(class)
+		}
+
+		/// Mint an NFT, owned by this contract unless `owner` is given
+		#[ink(message)]
+		pub fn mint_nft(
+			&mut self,
+			class: ClassId,
+			instance: InstanceId,
+			owner: Option<AccountId>,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_nft_mint(class, instance, owner)
+		}
+
+		/// Burn an NFT in a class administered by this contract
+		#[ink(message)]
+		pub fn burn_nft(
+			&mut self,
+			class: ClassId,
+			instance: InstanceId,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_nft_burn(class, instance)
+		}
+
+		/// Transfer an NFT owned or administered by this contract
+		#[ink(message)]
+		pub fn transfer_nft(
+			&mut self,
+			class: ClassId,
+			instance: InstanceId,
+			dest: AccountId,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_nft_transfer(class, instance, dest)
+		}
+
+		/// Set an attribute on a class administered by this contract, or on one of its NFTs
+		#[ink(message)]
+		pub fn set_nft_attribute(
+			&mut self,
+			class: ClassId,
+			instance: Option<InstanceId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_nft_set_attribute(class, instance, key, value)
+		}
+
+		/// Owner of an NFT, if it exists
+		#[ink(message)]
+		pub fn nft_owner_of(
+			&self,
+			class: ClassId,
+			instance: InstanceId,
+		) -> Result<Option<AccountId>, ContractError> {
+			self.env().extension().do_nft_owner_of(class, instance)
+		}
+
+		/// Attribute of a class, or of one of its NFTs
+		#[ink(message)]
+		pub fn nft_attribute(
+			&self,
+			class: ClassId,
+			instance: Option<InstanceId>,
+			key: Vec<u8>,
+		) -> Result<Option<Vec<u8>>, ContractError> {
+			self.env().extension().do_nft_attribute(class, instance, key)
+		}
+
+		/// Dispatch a SCALE-encoded runtime call with this contract as the signed origin
+		#[ink(message)]
+		pub fn dispatch_call(&mut self, call: Vec<u8>) -> Result<(), ContractError> {
+			self.ensure_owner()?;
+			self.env().extension().do_dispatch_call(call)
+		}
+
+		/// Randomness from the runtime for the given subject
+		#[ink(message)]
+		pub fn random(&self, subject: Vec<u8>) -> Result<RandomOutput, ContractError> {
+			self.env().extension().do_random(subject)
+		}
+
+		/// Check an sr25519 signature of `message`, e.g. a voucher signed off-chain
+		#[ink(message)]
+		pub fn verify_sr25519(
+			&self,
+			signature: [u8; 64],
+			message: Vec<u8>,
+			public: [u8; 32],
+		) -> Result<bool, ContractError> {
+			self.env().extension().do_sr25519_verify(signature, message, public)
+		}
+
+		/// Check an ed25519 signature of `message`
+		#[ink(message)]
+		pub fn verify_ed25519(
+			&self,
+			signature: [u8; 64],
+			message: Vec<u8>,
+			public: [u8; 32],
+		) -> Result<bool, ContractError> {
+			self.env().extension().do_ed25519_verify(signature, message, public)
+		}
+
+		/// Check an ecdsa signature of `message`
+		#[ink(message)]
+		pub fn verify_ecdsa(
+			&self,
+			signature: [u8; 65],
+			message: Vec<u8>,
+			public: [u8; 33],
+		) -> Result<bool, ContractError> {
+			self.env().extension().do_ecdsa_verify(signature, message, public)
+		}
+
+		/// Recover the ecdsa public key that signed `message`
+		#[ink(message)]
+		pub fn recover_ecdsa(
+			&self,
+			signature: [u8; 65],
+			message: Vec<u8>,
+		) -> Result<Option<[u8; 33]>, ContractError> {
+			self.env().extension().do_ecdsa_recover(signature, message)
+		}
+
+		/// Read a whitelisted runtime storage value, returning its raw SCALE encoding
+		#[ink(message)]
+		pub fn read_runtime_storage(&self, key: Vec<u8>) -> Result<Option<Vec<u8>>, ContractError> {
+			self.env().extension().do_read_storage(key)
+		}
+
+		/// Get the current storage value by reading `TemplateModule::ContractEntry` directly,
+		/// rather than through the dedicated `do_get_from_runtime`
+		#[ink(message)]
+		pub fn read_contract_entry(&self) -> Result<u32, ContractError> {
+			let raw = self
+				.env()
+				.extension()
+				.do_read_storage(storage_keys::TEMPLATE_MODULE_CONTRACT_ENTRY.to_vec())?;
+			match raw {
+				Some(raw) => Ok(u32::decode(&mut &raw[..])?),
+				// `ContractEntry` is a `ValueQuery`, so a missing value reads as the default
+				None => Ok(Default::default()),
+			}
+		}
+
+		/// Signal the runtime with a topic and payload, observable as a typed runtime event
+		#[ink(message)]
+		pub fn emit_signal(
+			&mut self,
+			topic: [u8; 32],
+			payload: Vec<u8>,
+		) -> Result<(), ContractError> {
+			self.env().extension().do_emit_signal(topic, payload)
+		}
+
+		/// Subscribe to transfers of the native currency to the contract, which `on_transfer` then
+		/// counts in the stored value
+		#[ink(message)]
+		pub fn subscribe_to_transfers(&mut self) -> Result<(), ContractError> {
+			// `TransferTo`, the first variant of the runtime's `EventFilter`, of the contract
+			let filter = (0u8, self.env().account_id()).encode();
+			self.env().extension().do_subscribe(filter, ON_TRANSFER_SELECTOR)
+		}
+
+		/// Stop counting transfers to the contract
+		#[ink(message)]
+		pub fn unsubscribe_from_transfers(&mut self) -> Result<(), ContractError> {
+			let filter = (0u8, self.env().account_id()).encode();
+			self.env().extension().do_unsubscribe(filter)
+		}
+
+		/// Called by the contract itself with each transfer to it, once subscribed. The runtime
+		/// event is passed SCALE-encoded, the contract only counts it.
+		#[ink(message, selector = 0x0E7E0001)]
+		pub fn on_transfer(&mut self, _event: Vec<u8>) {
+			// Others could call it too, but only deliveries come from the contract itself
+			if self.env().caller() != self.env().account_id() {
+				return
+			}
+			self.stored_number += 1;
+			self.env().emit_event(ResultNum { number: self.stored_number });
+		}
+
+		/// Request a number described by `payload` from the runtime's fulfillers, which
+		/// `on_number_fulfilled` then stores. Returns the id of the request.
+		#[ink(message)]
+		pub fn request_number(&mut self, payload: Vec<u8>) -> Result<u64, ContractError> {
+			self.env().extension().do_request(
+				ON_NUMBER_FULFILLED_SELECTOR,
+				REQUEST_GAS_LIMIT,
+				payload,
+			)
+		}
+
+		/// Called by the contract itself once request `id` is fulfilled, storing the number it
+		/// is fulfilled with if `result` is a SCALE-encoded `u32`.
+		#[ink(message, selector = 0x0E7E0002)]
+		pub fn on_number_fulfilled(&mut self, _id: u64, result: Vec<u8>) {
+			// Others could call it too, but only call backs come from the contract itself
+			if self.env().caller() != self.env().account_id() {
+				return
+			}
+			if let Ok(number) = u32::decode(&mut &result[..]) {
+				self.stored_number = number;
+				self.env().emit_event(ResultNum { number });
+			}
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use ink_lang as ink;
+		use scale::Encode;
+
+		type Event = <RuntimeInterface as ::ink_lang::BaseEvent>::Type;
+
+		/// Chain extension function `func_id` expecting `input` and returning `status`, writing
+		/// `output` if it succeeds.
+		struct MockExtension {
+			func_id: u32,
+			input: Vec<u8>,
+			status: u32,
+			output: Vec<u8>,
+		}
+
+		impl ink_env::test::ChainExtension for MockExtension {
+			fn func_id(&self) -> u32 {
+				self.func_id
+			}
+
+			fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+				assert_eq!(input, &self.input[..], "unexpected input to {}", self.func_id);
+				if self.status == 0 {
+					output.extend_from_slice(&self.output);
+				}
+				self.status
+			}
+		}
+
+		fn register(func_id: u32, input: impl Encode, status: u32, output: impl Encode) {
+			ink_env::test::register_chain_extension(MockExtension {
+				func_id,
+				input: input.encode(),
+				status,
+				output: output.encode(),
+			});
+		}
+
+		/// Every status code the runtime returns for a failure, and the error it is turned into,
+		/// with a code unknown to the bindings.
+		const ERRORS: [(u32, ContractError); 12] = [
+			(1, ContractError::InvalidCall),
+			(2, ContractError::CallFiltered),
+			(3, ContractError::DispatchFailed),
+			(4, ContractError::StorageKeyNotAllowed),
+			(5, ContractError::SignalPayloadTooLarge),
+			(6, ContractError::InputTooLarge),
+			(7, ContractError::ReadOnlyContext),
+			(8, ContractError::Retired),
+			(9, ContractError::NotPermitted),
+			(10, ContractError::TooManySubscriptions),
+			(11, ContractError::TooManyRequests),
+			(99, ContractError::UnknownStatusCode),
+		];
+
+		/// Makes `caller` the caller of the next messages.
+		fn set_caller(caller: AccountId) {
+			let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
+				.unwrap_or_else(|_| [0x07; 32].into());
+			ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+				caller,
+				callee,
+				1_000_000,
+				1_000_000,
+				ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+			);
+		}
+
+		fn bob() -> AccountId {
+			ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+				.expect("off-chain environment is initialized")
+				.bob
+		}
+
+		/// Numbers of the `ResultNum` events emitted so far.
+		fn result_nums() -> Vec<u32> {
+			ink_env::test::recorded_events()
+				.map(|event| match Event::decode(&mut &event.data[..]).expect("is an event") {
+					Event::ResultNum(ResultNum { number }) => number,
+				})
+				.collect()
+		}
+
+		#[ink::test]
+		fn set_value_stores_number() {
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.set_value(7), Ok(()));
+			assert_eq!(contract.get_value(), 7);
+			assert_eq!(result_nums(), vec![7, 7]);
+		}
+
+		#[ink::test]
+		fn store_in_runtime_works() {
+			register(1, 5u32, 0, ());
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.store_in_runtime(5), Ok(()));
+			assert_eq!(result_nums(), vec![5]);
+			// The number is stored in the runtime rather than in the contract
+			assert_eq!(contract.stored_number, 0);
+		}
+
+		#[ink::test]
+		fn store_in_runtime_returns_errors() {
+			let mut contract = RuntimeInterface::default();
+			for (status, error) in ERRORS.iter() {
+				register(1, 5u32, *status, ());
+				assert_eq!(contract.store_in_runtime(5), Err(*error));
+			}
+			assert_eq!(result_nums(), vec![]);
+			assert_eq!(contract.stored_number, 0);
+		}
+
+		#[ink::test]
+		fn extended_transfer_works() {
+			register(65538, (100u128, bob()), 0, ());
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.extended_transfer(100, bob()), Ok(()));
+		}
+
+		#[ink::test]
+		fn extended_transfer_returns_errors() {
+			let mut contract = RuntimeInterface::default();
+			for (status, error) in ERRORS.iter() {
+				register(65538, (100u128, bob()), *status, ());
+				assert_eq!(contract.extended_transfer(100, bob()), Err(*error));
+			}
+		}
+
+		#[ink::test]
+		fn get_balance_works() {
+			register(3, bob(), 0, 1_000u128);
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.get_balance(bob()), Ok(1_000));
+		}
+
+		#[ink::test]
+		fn get_balance_returns_errors() {
+			let mut contract = RuntimeInterface::default();
+			for (status, error) in ERRORS.iter() {
+				register(3, bob(), *status, 1_000u128);
+				assert_eq!(contract.get_balance(bob()), Err(*error));
+			}
+		}
+
+		#[ink::test]
+		fn get_balance_rejects_invalid_output() {
+			// A balance is 16 bytes, a `u32` is too short to decode one
+			register(3, bob(), 0, 1_000u32);
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.get_balance(bob()), Err(ContractError::InvalidScaleEncoding));
+		}
+
+		#[ink::test]
+		fn get_runtime_storage_value_works() {
+			register(4, (), 0, 42u32);
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.get_runtime_storage_value(), Ok(42));
+			assert_eq!(result_nums(), vec![42]);
+			assert_eq!(contract.stored_number, 0);
+		}
+
+		#[ink::test]
+		fn get_runtime_storage_value_returns_errors() {
+			let mut contract = RuntimeInterface::default();
+			for (status, error) in ERRORS.iter() {
+				register(4, (), *status, 42u32);
+				assert_eq!(contract.get_runtime_storage_value(), Err(*error));
+			}
+			assert_eq!(result_nums(), vec![]);
+		}
+
+		#[ink::test]
+		fn asset_functions_are_only_permitted_to_the_owner() {
+			register(102, (7u32, bob(), 100u128), 0, ());
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.mint_asset(7, bob(), 100), Ok(()));
+
+			set_caller(bob());
+			assert_eq!(contract.mint_asset(7, bob(), 100), Err(ContractError::NotPermitted));
+			assert_eq!(contract.transfer_asset(7, bob(), 100), Err(ContractError::NotPermitted));
+			assert_eq!(contract.approve_asset(7, bob(), 100), Err(ContractError::NotPermitted));
+			assert_eq!(
+				contract.transfer_asset_from(7, bob(), bob(), 100),
+				Err(ContractError::NotPermitted)
+			);
+		}
+
+		#[ink::test]
+		fn nft_functions_are_only_permitted_to_the_owner() {
+			register(204, (1u32, 2u32, bob()), 0, ());
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.transfer_nft(1, 2, bob()), Ok(()));
+
+			set_caller(bob());
+			assert_eq!(contract.transfer_nft(1, 2, bob()), Err(ContractError::NotPermitted));
+			assert_eq!(contract.mint_nft(1, 3, None), Err(ContractError::NotPermitted));
+			assert_eq!(contract.burn_nft(1, 2), Err(ContractError::NotPermitted));
+		}
+
+		#[ink::test]
+		fn dispatch_call_is_only_permitted_to_the_owner() {
+			register(301, vec![0u8, 1], 0, ());
+			let mut contract = RuntimeInterface::default();
+			assert_eq!(contract.dispatch_call(vec![0, 1]), Ok(()));
+
+			set_caller(bob());
+			assert_eq!(contract.dispatch_call(vec![0, 1]), Err(ContractError::NotPermitted));
+		}
 	}
 }
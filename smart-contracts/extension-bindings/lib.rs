@@ -0,0 +1,320 @@
+//! ink! bindings of the chain extension of the node template runtime.
+//!
+//! Generated from the runtime's chain extension definitions, do not edit. Regenerate with
+//! `node-template chain-extension-bindings > smart-contracts/extension-bindings/lib.rs`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#[allow(unused_imports)]
+use ink_env::{AccountId, Hash};
+use ink_lang as ink;
+#[allow(unused_imports)]
+use ink_prelude::vec::Vec;
+
+#[ink::chain_extension]
+pub trait ChainExtension {
+	type ErrorCode = ContractError;
+
+	/// Stores `value` in `pallet_template`, as the caller of the contract.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 1)]
+	fn do_store_in_runtime(value: u32) -> Result<(), ContractError>;
+
+	/// Transfers `value` of the native currency from the caller of the contract to `recipient`.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 65538)]
+	fn do_balance_transfer(value: u128, recipient: AccountId) -> Result<(), ContractError>;
+
+	/// Returns the free balance of `account`.
+	#[ink(extension = 3)]
+	fn do_get_balance(account: AccountId) -> Result<u128, ContractError>;
+
+	/// Returns the value stored in `pallet_template`.
+	#[ink(extension = 4)]
+	fn do_get_from_runtime() -> Result<u32, ContractError>;
+
+	/// Creates a new runtime asset with the given id. The contract becomes its owner and admin.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 101)]
+	fn do_asset_create(asset_id: u32, min_balance: u128) -> Result<(), ContractError>;
+
+	/// Mints `amount` of the asset to `beneficiary`. Only succeeds for assets the contract issues.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 102)]
+	fn do_asset_mint(asset_id: u32, beneficiary: AccountId, amount: u128) -> Result<(), ContractError>;
+
+	/// Burns up to `amount` of the asset held by `who`. Only succeeds for assets the contract
+	/// administers.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 103)]
+	fn do_asset_burn(asset_id: u32, who: AccountId, amount: u128) -> Result<(), ContractError>;
+
+	/// Transfers `amount` of the asset from the contract's own balance to `target`.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 104)]
+	fn do_asset_transfer(asset_id: u32, target: AccountId, amount: u128) -> Result<(), ContractError>;
+
+	/// Approves `delegate` to transfer up to `amount` of the asset out of the contract's own
+	/// balance.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 105)]
+	fn do_asset_approve(asset_id: u32, delegate: AccountId, amount: u128) -> Result<(), ContractError>;
+
+	/// Transfers `amount` of the asset from `owner` to `destination`, using an approval previously
+	/// given by `owner` to the contract.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 106)]
+	fn do_asset_transfer_approved(asset_id: u32, owner: AccountId, destination: AccountId, amount: u128) -> Result<(), ContractError>;
+
+	/// Returns the asset balance of `who`.
+	#[ink(extension = 107)]
+	fn do_asset_balance_of(asset_id: u32, who: AccountId) -> Result<u128, ContractError>;
+
+	/// Returns the total issuance of the asset.
+	#[ink(extension = 108)]
+	fn do_asset_total_supply(asset_id: u32) -> Result<u128, ContractError>;
+
+	/// Returns the amount `delegate` is still allowed to transfer out of `owner`'s balance.
+	#[ink(extension = 109)]
+	fn do_asset_allowance(asset_id: u32, owner: AccountId, delegate: AccountId) -> Result<u128, ContractError>;
+
+	/// Creates a new NFT class administered by the contract.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 201)]
+	fn do_nft_create_class(class: u32) -> Result<(), ContractError>;
+
+	/// Mints an NFT in a class administered by the contract. The contract owns the new instance
+	/// unless another `owner` is given.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 202)]
+	fn do_nft_mint(class: u32, instance: u32, owner: Option<AccountId>) -> Result<(), ContractError>;
+
+	/// Burns an NFT in a class administered by the contract.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 203)]
+	fn do_nft_burn(class: u32, instance: u32) -> Result<(), ContractError>;
+
+	/// Transfers an NFT owned or administered by the contract to `dest`.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 204)]
+	fn do_nft_transfer(class: u32, instance: u32, dest: AccountId) -> Result<(), ContractError>;
+
+	/// Sets an attribute on a class administered by the contract, or on one of its instances.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 205)]
+	fn do_nft_set_attribute(class: u32, instance: Option<u32>, key: Vec<u8>, value: Vec<u8>) -> Result<(), ContractError>;
+
+	/// Returns the owner of an NFT, if it exists.
+	#[ink(extension = 206)]
+	fn do_nft_owner_of(class: u32, instance: u32) -> Result<Option<AccountId>, ContractError>;
+
+	/// Returns an attribute of a class, or of one of its instances, if it is set.
+	#[ink(extension = 207)]
+	fn do_nft_attribute(class: u32, instance: Option<u32>, key: Vec<u8>) -> Result<Option<Vec<u8>>, ContractError>;
+
+	/// Dispatches a SCALE-encoded runtime call with the contract as the signed origin. Only calls
+	/// allowed by the runtime's call filter are dispatched.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 301)]
+	fn do_dispatch_call(call: Vec<u8>) -> Result<(), ContractError>;
+
+	/// Dispatches a SCALE-encoded runtime call with the `pallet_template::ContractOrigin` of the
+	/// contract as origin, for calls requiring it to come from a given contract or contract code.
+	/// Only calls allowed by the runtime's call filter are dispatched.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 302)]
+	fn do_dispatch_call_as_contract(call: Vec<u8>) -> Result<(), ContractError>;
+
+	/// Returns randomness derived from `subject`. The seed has been knowable to anyone, including
+	/// block authors, since the block it was determined at, and changes with every block. Only use
+	/// it to settle something committed to strictly before that block, with the block fixed when
+	/// committing, or whoever picks when to read it picks the seed.
+	#[ink(extension = 401)]
+	fn do_random(subject: Vec<u8>) -> Result<RandomOutput, ContractError>;
+
+	/// Verifies an sr25519 `signature` of `message` by `public`.
+	#[ink(extension = 501)]
+	fn do_sr25519_verify(signature: [u8; 64], message: Vec<u8>, public: [u8; 32]) -> Result<bool, ContractError>;
+
+	/// Verifies an ed25519 `signature` of `message` by `public`.
+	#[ink(extension = 502)]
+	fn do_ed25519_verify(signature: [u8; 64], message: Vec<u8>, public: [u8; 32]) -> Result<bool, ContractError>;
+
+	/// Verifies an ECDSA `signature` of the blake2-256 hash of `message` by the compressed public
+	/// key `public`.
+	#[ink(extension = 503)]
+	fn do_ecdsa_verify(signature: [u8; 65], message: Vec<u8>, public: [u8; 33]) -> Result<bool, ContractError>;
+
+	/// Recovers the compressed ECDSA public key that signed the blake2-256 hash of `message`.
+	#[ink(extension = 504)]
+	fn do_ecdsa_recover(signature: [u8; 65], message: Vec<u8>) -> Result<Option<[u8; 33]>, ContractError>;
+
+	/// Reads the raw value stored under `key` in runtime storage. Only keys starting with one of
+	/// the prefixes readable by contracts can be read.
+	#[ink(extension = 601)]
+	fn do_read_storage(key: Vec<u8>) -> Result<Option<Vec<u8>>, ContractError>;
+
+	/// Makes the runtime deposit a typed `TemplateModule::ContractSignal` event carrying the
+	/// contract's address, `topic` and `payload`.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 701)]
+	fn do_emit_signal(topic: [u8; 32], payload: Vec<u8>) -> Result<(), ContractError>;
+
+	/// Subscribes the contract to the runtime events matching `filter`, a SCALE-encoded runtime
+	/// `EventFilter`, from the next block. The events of a block matching it are delivered at the
+	/// start of the next: the contract calls itself with each of them, its message with
+	/// `selector` getting the SCALE-encoded event as a `Vec<u8>`. Subscribing again with the same
+	/// `filter` replaces the selector. A deposit is reserved from the contract for each
+	/// subscription, returned when it unsubscribes, and traps the contract if it can't pay it.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 702)]
+	fn do_subscribe(filter: Vec<u8>, selector: [u8; 4]) -> Result<(), ContractError>;
+
+	/// Removes the subscription of the contract to the runtime events matching `filter`, if any,
+	/// returning its deposit.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 703)]
+	fn do_unsubscribe(filter: Vec<u8>) -> Result<(), ContractError>;
+
+	/// Requests a result only available later, such as off-chain data, described by `payload` in
+	/// a format agreed on with the fulfillers, returning the request id. Once fulfilled, the
+	/// contract calls itself back: its message with `selector` gets the id and the result as
+	/// `(u64, Vec<u8>)` arguments, given `gas_limit`. The gas limit, at least the runtime's
+	/// `MinRequestGasLimit`, and the fulfilment are charged now. A deposit is reserved from the
+	/// contract until the request is fulfilled or, once `RequestTimeout` blocks passed, expired by
+	/// anyone. Traps the contract if it can't pay the deposit.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 704)]
+	fn do_request(selector: [u8; 4], gas_limit: u64, payload: Vec<u8>) -> Result<u64, ContractError>;
+
+	/// Returns the latest value aggregated by the oracle for `key`, the median of the values fed
+	/// for it which were not stale, and when it was aggregated in milliseconds since the Unix
+	/// epoch, or `None` if no value was ever fed for `key`.
+	#[ink(extension = 705)]
+	fn do_oracle_value(key: u32) -> Result<Option<TimestampedValue>, ContractError>;
+
+	/// Notes `encoded_proposal`, a SCALE-encoded runtime call, as a `Democracy` preimage. The
+	/// deposit is reserved from the contract. Only permitted contracts may call it.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 801)]
+	fn do_democracy_note_preimage(encoded_proposal: Vec<u8>) -> Result<(), ContractError>;
+
+	/// Submits the proposal with preimage hash `proposal_hash` to `Democracy`, locking `value` of
+	/// the contract's balance as deposit. Only permitted contracts may call it.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 802)]
+	fn do_democracy_propose(proposal_hash: Hash, value: u128) -> Result<(), ContractError>;
+
+	/// Seconds public proposal `proposal`, which has no more than `seconds_upper_bound` seconds,
+	/// with the contract's balance. Only permitted contracts may call it.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 803)]
+	fn do_democracy_second(proposal: u32, seconds_upper_bound: u32) -> Result<(), ContractError>;
+
+	/// Votes on referendum `ref_index`, locking `balance` of the contract's balance with
+	/// `conviction`, from 0 (no lock) to 6. Only permitted contracts may call it.
+	///
+	/// Changes state, so it fails with `ContractError::ReadOnlyContext` in queries.
+	#[ink(extension = 804)]
+	fn do_democracy_vote(ref_index: u32, aye: bool, conviction: u8, balance: u128) -> Result<(), ContractError>;
+}
+
+/// Output of the chain extension functions returning it, see their documentation.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RandomOutput {
+	pub seed: Hash,
+	pub determined_at: u32,
+}
+
+/// Output of the chain extension functions returning it, see their documentation.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TimestampedValue {
+	pub value: u128,
+	pub timestamp: u64,
+}
+
+/// Errors of the chain extension functions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ContractError {
+	/// The chain extension could not be called.
+	FailToCallRuntime,
+	/// The runtime returned a status code unknown to these bindings.
+	UnknownStatusCode,
+	/// The output of the function could not be decoded.
+	InvalidScaleEncoding,
+	/// The runtime call passed to the function could not be decoded.
+	InvalidCall,
+	/// The runtime call is not allowed by the runtime's call filter.
+	CallFiltered,
+	/// The runtime call was dispatched but returned an error.
+	DispatchFailed,
+	/// The storage key does not start with any of the prefixes contracts may read.
+	StorageKeyNotAllowed,
+	/// The signal payload is longer than `pallet_template::Config::MaxSignalPayloadLen`.
+	SignalPayloadTooLarge,
+	/// The input is longer than `Config::max_input_len` allows for the function.
+	InputTooLarge,
+	/// The function changes state but the contract is executed as a read-only query.
+	ReadOnlyContext,
+	/// The function is a deprecated version the runtime no longer serves.
+	Retired,
+	/// The contract is not allowed to call the function.
+	NotPermitted,
+	/// The contract, or all contracts together, have as many event subscriptions as allowed.
+	TooManySubscriptions,
+	/// The contract, or all contracts together, have as many requests waiting to be fulfilled as
+	/// allowed.
+	TooManyRequests,
+}
+
+impl From<scale::Error> for ContractError {
+	fn from(_: scale::Error) -> Self {
+		ContractError::InvalidScaleEncoding
+	}
+}
+
+impl ink_env::chain_extension::FromStatusCode for ContractError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			1 => Err(Self::InvalidCall),
+			2 => Err(Self::CallFiltered),
+			3 => Err(Self::DispatchFailed),
+			4 => Err(Self::StorageKeyNotAllowed),
+			5 => Err(Self::SignalPayloadTooLarge),
+			6 => Err(Self::InputTooLarge),
+			7 => Err(Self::ReadOnlyContext),
+			8 => Err(Self::Retired),
+			9 => Err(Self::NotPermitted),
+			10 => Err(Self::TooManySubscriptions),
+			11 => Err(Self::TooManyRequests),
+			_ => Err(Self::UnknownStatusCode),
+		}
+	}
+}